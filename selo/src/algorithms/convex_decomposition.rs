@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use bevy_math::DVec2;
+
+use crate::{errors::GeometryError, Polygon, Ring, TriangulationConfig};
+
+use super::triangulate_polygon;
+
+/// Hertel-Mehlhorn decomposition: starting from a triangulation, diagonals are removed as long as
+/// the two pieces next to them form a convex polygon. The result has at most four times as many
+/// pieces as an optimal decomposition. All pieces are counter-clockwise.
+pub(crate) fn convex_decomposition(
+    polygon: &Polygon<DVec2>,
+    config: TriangulationConfig,
+) -> Result<Vec<Ring<DVec2>>, GeometryError> {
+    let mesh = triangulate_polygon(polygon, config)?;
+    let vertices = mesh.vertices;
+
+    let mut pieces = mesh
+        .triangles
+        .into_iter()
+        .map(|triangle| Some(triangle.to_vec()))
+        .collect::<Vec<_>>();
+    let mut piece_of = HashMap::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        for edge in edges(piece.as_deref().unwrap_or_default()) {
+            piece_of.insert(edge, i);
+        }
+    }
+
+    // removing long diagonals first tends to leave fewer pieces
+    let mut diagonals = piece_of
+        .keys()
+        .filter(|(a, b)| a < b && piece_of.contains_key(&(*b, *a)))
+        .copied()
+        .collect::<Vec<_>>();
+    diagonals.sort_by(|(a, b), (c, d)| {
+        let length = |i: &usize, j: &usize| vertices[*i].distance_squared(vertices[*j]);
+        length(c, d)
+            .total_cmp(&length(a, b))
+            .then((a, b).cmp(&(c, d)))
+    });
+
+    let (min, max) = vertices
+        .iter()
+        .fold((DVec2::INFINITY, DVec2::NEG_INFINITY), |(min, max), p| {
+            (min.min(*p), max.max(*p))
+        });
+    let eps = (max - min).length_squared() * 1e-12;
+
+    for (a, b) in diagonals {
+        let (Some(&p), Some(&q)) = (piece_of.get(&(a, b)), piece_of.get(&(b, a))) else {
+            continue;
+        };
+        if p == q {
+            continue;
+        }
+        let (Some(first), Some(second)) = (pieces[p].as_ref(), pieces[q].as_ref()) else {
+            continue;
+        };
+        // `first` runs from `b` around to `a`, `second` from `a` around to `b`
+        let first = rotated(first, b);
+        let second = rotated(second, a);
+        let merged = first
+            .iter()
+            .chain(&second[1..second.len() - 1])
+            .copied()
+            .collect::<Vec<_>>();
+
+        let n = merged.len();
+        let convex_at = |k: usize| {
+            let [prev, v, next] = [(k + n - 1) % n, k, (k + 1) % n].map(|i| vertices[merged[i]]);
+            (v - prev).perp_dot(next - v) >= -eps
+        };
+        if !(convex_at(0) && convex_at(first.len() - 1)) {
+            continue;
+        }
+
+        piece_of.remove(&(a, b));
+        piece_of.remove(&(b, a));
+        for edge in edges(&second) {
+            if let Some(piece) = piece_of.get_mut(&edge) {
+                *piece = p;
+            }
+        }
+        pieces[p] = Some(merged);
+        pieces[q] = None;
+    }
+
+    Ok(pieces
+        .into_iter()
+        .flatten()
+        .map(|piece| Ring::new(piece.into_iter().map(|i| vertices[i]).collect::<Vec<_>>()))
+        .collect())
+}
+
+fn edges(piece: &[usize]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    piece
+        .iter()
+        .zip(piece.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+fn rotated(piece: &[usize], start: usize) -> Vec<usize> {
+    let offset = piece.iter().position(|i| *i == start).unwrap_or_default();
+    piece[offset..]
+        .iter()
+        .chain(&piece[..offset])
+        .copied()
+        .collect()
+}
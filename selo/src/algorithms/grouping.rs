@@ -1,26 +1,278 @@
-use bevy_math::Vec3;
+use std::collections::HashMap;
 
-use crate::{prelude::Workplane, Embed, IterPoints, Normal};
+use rayon::prelude::*;
 
-/// Group primitives based on their plane
-pub fn group_primitives<T: IterPoints<P = Vec3> + Normal<P = Vec3> + Embed>(
+use crate::{
+    prelude::{EmbeddingPlane, WorkplanePoint},
+    Embed, IterPoints, Normal, Point, Point3,
+};
+
+type WorkplaneOf<T> = <<T as IterPoints>::P as WorkplanePoint>::Workplane;
+
+/// Configures when [`group_primitives_with`] considers primitives to lie in the same plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupingOptions<S> {
+    /// The maximum difference of each component of the unit normals and of the distances of the
+    /// planes to the origin
+    pub tolerance: S,
+    /// Whether planes facing in opposite directions end up in the same group. Primitives facing
+    /// away from the normal of their group are mirrored by the embedding, i.e. their 2D
+    /// counterparts have the opposite winding.
+    pub merge_anti_parallel: bool,
+}
+
+/// Group primitives based on their plane. Primitives with `Vec3` coordinates are grouped by
+/// [`Workplane`](crate::prelude::Workplane), the ones with `DVec3` coordinates by
+/// [`DWorkplane`](crate::prelude::DWorkplane).
+///
+/// Shorthand for [`group_primitives_with`] without merging anti-parallel planes.
+#[allow(clippy::type_complexity)]
+pub fn group_primitives<T>(
     p: impl IntoIterator<Item = T>,
-    tolerance: f32,
-) -> Vec<(Workplane, Vec<T::Type2D>)> {
-    let mut groups: Vec<(Workplane, Vec<T::Type2D>)> = vec![];
-    for p in p {
-        let Ok(wp) = Workplane::from_primitive(&p) else {
-            continue;
-        };
-        let wp = wp.hesse_normal_form();
-        if let Some((wp, group)) = groups.iter_mut().find(|(other, _)| {
-            wp.plane.normal.abs_diff_eq(*other.plane.normal, tolerance)
-                && wp.origin.abs_diff_eq(other.origin, tolerance)
-        }) {
-            group.push(p.embed(*wp));
-        } else {
-            groups.push((wp, vec![p.embed(wp)]))
+    tolerance: <<T as IterPoints>::P as Point>::S,
+) -> Vec<(WorkplaneOf<T>, Vec<<T as Embed<WorkplaneOf<T>>>::Type2D>)>
+where
+    T: IterPoints + Normal<P = <T as IterPoints>::P> + Embed<WorkplaneOf<T>> + Sync,
+    <T as Embed<WorkplaneOf<T>>>::Type2D: Send,
+    <T as IterPoints>::P: WorkplanePoint,
+    WorkplaneOf<T>: Send + Sync,
+{
+    group_primitives_with(
+        p,
+        GroupingOptions {
+            tolerance,
+            merge_anti_parallel: false,
+        },
+    )
+}
+
+/// Group primitives based on their plane, see [`group_primitives`].
+///
+/// The groups are in the order of their first primitive, which also determines the plane of the
+/// group. Primitives without a valid plane, e.g. degenerate triangles, are skipped. The planes
+/// and the embeddings are computed in parallel and the lookup of matching groups goes through a
+/// spatial hash, so this scales to hundreds of thousands of primitives.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let square = |z: f32| {
+///     Ring::new([Vec3::ZERO, Vec3::X, Vec3::new(1.0, 1.0, 0.0), Vec3::Y].map(|p| p + Vec3::Z * z))
+/// };
+/// // the floor slab seen from above and from below
+/// let primitives = [square(0.0), square(3.0), square(0.0).flip()];
+///
+/// let separate = group_primitives(primitives.clone(), 0.001);
+/// let merged = group_primitives_with(
+///     primitives,
+///     GroupingOptions { tolerance: 0.001, merge_anti_parallel: true },
+/// );
+///
+/// assert_eq!(separate.len(), 3);
+/// assert_eq!(merged.len(), 2);
+/// assert_eq!(merged[0].1.len(), 2);
+/// // the bottom face is mirrored into the plane of the top face
+/// assert!(merged[0].1[1].area() < 0.0);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn group_primitives_with<T>(
+    p: impl IntoIterator<Item = T>,
+    options: GroupingOptions<<<T as IterPoints>::P as Point>::S>,
+) -> Vec<(WorkplaneOf<T>, Vec<<T as Embed<WorkplaneOf<T>>>::Type2D>)>
+where
+    T: IterPoints + Normal<P = <T as IterPoints>::P> + Embed<WorkplaneOf<T>> + Sync,
+    <T as Embed<WorkplaneOf<T>>>::Type2D: Send,
+    <T as IterPoints>::P: WorkplanePoint,
+    WorkplaneOf<T>: Send + Sync,
+{
+    let primitives = p.into_iter().collect::<Vec<_>>();
+    let planes = primitives
+        .par_iter()
+        .map(|p| {
+            WorkplaneOf::<T>::from_primitive(p)
+                .ok()
+                .map(|wp| wp.hesse_normal_form())
+        })
+        .collect::<Vec<_>>();
+
+    let mut index = PlaneIndex::new(options.tolerance.into());
+    let membership = planes
+        .into_iter()
+        .map(|wp| {
+            let wp = wp?;
+            let (normal, distance) = wp.normal_and_distance();
+            let key = [normal.x(), normal.y(), normal.z(), distance].map(Into::into);
+            let flipped = key.map(|c: f64| -c);
+            let found = index.find(key);
+            let found = match (found, options.merge_anti_parallel) {
+                (found, true) => found.into_iter().chain(index.find(flipped)).min(),
+                (found, false) => found,
+            };
+            Some(found.unwrap_or_else(|| index.insert(key, wp)))
+        })
+        .collect::<Vec<_>>();
+
+    let embedded = primitives
+        .par_iter()
+        .zip(membership.par_iter())
+        .map(|(p, group)| group.map(|group| p.embed(index.planes[group])))
+        .collect::<Vec<_>>();
+    let mut groups = index
+        .planes
+        .into_iter()
+        .map(|wp| (wp, vec![]))
+        .collect::<Vec<_>>();
+    for (group, p) in membership.into_iter().zip(embedded) {
+        if let (Some(group), Some(p)) = (group, p) {
+            groups[group].1.push(p);
         }
     }
     groups
 }
+
+/// Planes hashed by the components of their normal and their distance to the origin. The cells
+/// are twice as large as the tolerance, so the matches of a plane lie in its own cell or in the
+/// neighbouring one towards the closer boundary in each of the four dimensions.
+struct PlaneIndex<W> {
+    planes: Vec<W>,
+    keys: Vec<[f64; 4]>,
+    cells: HashMap<[i64; 4], Vec<usize>>,
+    tolerance: f64,
+}
+
+impl<W> PlaneIndex<W> {
+    fn new(tolerance: f64) -> Self {
+        Self {
+            planes: vec![],
+            keys: vec![],
+            cells: HashMap::new(),
+            tolerance,
+        }
+    }
+
+    fn cell(&self, key: [f64; 4]) -> [f64; 4] {
+        key.map(|c| c / (2.0 * self.tolerance).max(f64::MIN_POSITIVE))
+    }
+
+    fn insert(&mut self, key: [f64; 4], plane: W) -> usize {
+        let cell = self.cell(key).map(|c| c.floor() as i64);
+        self.planes.push(plane);
+        self.keys.push(key);
+        self.cells
+            .entry(cell)
+            .or_default()
+            .push(self.planes.len() - 1);
+        self.planes.len() - 1
+    }
+
+    /// The first plane matching the key within the tolerance
+    fn find(&self, key: [f64; 4]) -> Option<usize> {
+        let cell = self.cell(key);
+        let base = cell.map(|c| c.floor() as i64);
+        let towards = cell.map(|c| if c - c.floor() < 0.5 { -1 } else { 1 });
+        (0..16)
+            .filter_map(|corner: usize| {
+                let neighbour: [i64; 4] = std::array::from_fn(|i| {
+                    base[i]
+                        + if corner & (1 << i) != 0 {
+                            towards[i]
+                        } else {
+                            0
+                        }
+                });
+                self.cells.get(&neighbour)
+            })
+            .flatten()
+            .copied()
+            .filter(|i| {
+                self.keys[*i]
+                    .iter()
+                    .zip(key)
+                    .all(|(a, b)| (a - b).abs() <= self.tolerance)
+            })
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod grouping_tests {
+    use bevy_math::{DVec3, Vec3};
+
+    use super::*;
+    use crate::{Area, Flip, Ring, Triangle};
+
+    #[test]
+    fn matches_across_cell_boundaries() {
+        let tolerance = 0.01;
+        // heights around the boundaries of the hash cells
+        let heights = [0.0, 0.0199, 0.0201, 0.0295, 0.0301, -0.0001, 1.0];
+        let triangles =
+            heights.map(|z| Triangle([Vec3::ZERO, Vec3::X, Vec3::Y].map(|p| p + Vec3::Z * z)));
+
+        let groups = group_primitives(triangles, tolerance);
+
+        let expected = [
+            [0.0, -0.0001].as_slice(),
+            &[0.0199, 0.0201, 0.0295],
+            &[0.0301],
+            &[1.0],
+        ];
+        assert_eq!(groups.len(), expected.len());
+        for ((wp, members), heights) in groups.iter().zip(expected) {
+            assert_eq!(members.len(), heights.len(), "{wp:?}");
+            assert!((wp.origin().z - heights[0]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn many_facades() {
+        // the faces of a row of boxes, all side faces of a row share their plane
+        let faces = (0..2000)
+            .flat_map(|i| {
+                let offset = DVec3::new(i as f64 * 10.0, 0.0, 0.0);
+                let quad = |a: DVec3, b: DVec3, c: DVec3, d: DVec3| {
+                    Ring::new([a, b, c, d].map(|p| p + offset))
+                };
+                let outside = [
+                    quad(DVec3::ZERO, DVec3::X, DVec3::new(1.0, 0.0, 1.0), DVec3::Z),
+                    quad(
+                        DVec3::Y,
+                        DVec3::new(0.0, 1.0, 1.0),
+                        DVec3::ONE,
+                        DVec3::new(1.0, 1.0, 0.0),
+                    ),
+                    quad(
+                        DVec3::Z,
+                        DVec3::new(1.0, 0.0, 1.0),
+                        DVec3::ONE,
+                        DVec3::new(0.0, 1.0, 1.0),
+                    ),
+                ];
+                // the faces seen from the inside
+                outside
+                    .clone()
+                    .into_iter()
+                    .chain(outside.map(|face| face.flip()))
+            })
+            .collect::<Vec<_>>();
+
+        let groups = group_primitives(faces.clone(), 1e-9);
+        assert_eq!(groups.len(), 6);
+        assert!(groups.iter().all(|(_, members)| members.len() == 2000));
+
+        let merged = group_primitives_with(
+            faces,
+            GroupingOptions {
+                tolerance: 1e-9,
+                merge_anti_parallel: true,
+            },
+        );
+        assert_eq!(merged.len(), 3);
+        for (_, members) in merged {
+            assert_eq!(members.len(), 4000);
+            assert!(members.iter().step_by(2).all(|p| p.area() > 0.0));
+            assert!(members.iter().skip(1).step_by(2).all(|p| p.area() < 0.0));
+        }
+    }
+}
@@ -1,6 +1,56 @@
 use num_traits::Float;
 
-use crate::{Line, Point2};
+use crate::{Line, Point, Point2, Point3};
+
+use super::{orient2d, Orientation};
+
+impl<P: Point> Line<P> {
+    /// The intersection of two collinear line segments. Returns `None` if they overlap by less
+    /// than the tolerance.
+    #[inline]
+    fn collinear_intersection(&self, o: &Self, tolerance: P::S) -> Option<Line2DIntersection<P>> {
+        let r = self.to_dst();
+
+        // Scalars to compare
+        // NOTE: We cannot use the unnormed scalar here as self might be very short, leading to rounding errors.
+        let ssn_src = <P::S>::from(0.0);
+        let ssn_dst = self.scalar_of_normed(self.dst());
+        let mut osn_src = self.scalar_of_normed(self.project(o.src()));
+        let mut osn_dst = self.scalar_of_normed(self.project(o.dst()));
+        if osn_src > osn_dst {
+            std::mem::swap(&mut osn_src, &mut osn_dst);
+        }
+
+        if osn_src > ssn_dst + tolerance || osn_dst < ssn_src - tolerance {
+            Some(Line2DIntersection::CollinearDisjoint)
+        } else if (osn_src - ssn_dst).abs() <= tolerance {
+            Some(Line2DIntersection::CollinearTouch(self.dst()))
+        } else if (osn_dst - ssn_src).abs() <= tolerance {
+            Some(Line2DIntersection::CollinearTouch(self.src()))
+        } else {
+            // Use unnormed scalars to calculate the overlapping segment
+            let overlap_src = r * <P::S>::from(0.0).max(osn_src / ssn_dst) + self.src();
+            let overlap_dst = r * <P::S>::from(1.0).min(osn_dst / ssn_dst) + self.src();
+
+            // We only return a line if a given threshold is met
+            (!overlap_src.abs_diff_eq(overlap_dst, tolerance)).then_some(
+                Line2DIntersection::CollinearOverlap(Line([overlap_src, overlap_dst])),
+            )
+        }
+    }
+
+    /// Where two collinear line segments touch if they overlap by less than the tolerance: in the
+    /// middle of the shorter one, which lies within the longer one
+    #[inline]
+    fn collinear_touch(&self, o: &Self) -> Line2DIntersection<P> {
+        let (short, long) = if self.length() <= o.length() {
+            (self, o)
+        } else {
+            (o, self)
+        };
+        Line2DIntersection::CollinearTouch(long.project(short.center()))
+    }
+}
 
 impl<P: Point2> Line<P> {
     /// Checks whether the two line segments intersect, where and whether the intersection happens within the given line
@@ -21,66 +71,278 @@ impl<P: Point2> Line<P> {
         // Lines are parallel or either line segment has len = 0 (but len = 0 is forbidden)
         // Parallel and/or collinear, potential overlaps.
 
+        // Exact sides of the endpoints relative to the other line, these decide the
+        // classification whenever rounding could flip it
+        let [o_src, o_dst] = o.0.map(|p| orient2d(self.src(), self.dst(), p));
+        let [s_src, s_dst] = self.0.map(|p| orient2d(o.src(), o.dst(), p));
+        let collinear = [o_src, o_dst, s_src, s_dst]
+            .iter()
+            .all(|side| *side == Orientation::Degenerate);
+
         // Parallel?
         let tolerance_relaxed = tolerance * <P::S>::from(10.0);
-        if det.abs() <= tolerance_relaxed {
+        if det.abs() <= tolerance_relaxed || collinear {
             let cp = (o.src() - self.src()).wedge(r);
 
-            if cp.abs() > tolerance_relaxed {
+            if cp.abs() > tolerance_relaxed && !collinear {
                 // Parallel and !collinear -> no intersection
                 return Line2DIntersection::ParallelNonCollinear;
             }
-
-            // Scalars to compare
-            // NOTE: We cannot use the unnormed scalar here as self might be very short, leading to rounding errors.
-            let ssn_src = <P::S>::from(0.0);
-            let ssn_dst = self.scalar_of_normed(self.dst());
-            let mut osn_src = self.scalar_of_normed(self.project(o.src()));
-            let mut osn_dst = self.scalar_of_normed(self.project(o.dst()));
-            if osn_src > osn_dst {
-                std::mem::swap(&mut osn_src, &mut osn_dst);
+            if let Some(intersection) = self.collinear_intersection(o, tolerance) {
+                return intersection;
             }
-
-            if osn_src > ssn_dst + tolerance || osn_dst < ssn_src - tolerance {
-                return Line2DIntersection::CollinearDisjoint;
-            } else if (osn_src - ssn_dst).abs() <= tolerance {
-                return Line2DIntersection::CollinearTouch(self.dst());
-            } else if (osn_dst - ssn_src).abs() <= tolerance {
-                return Line2DIntersection::CollinearTouch(self.src());
-            } else {
-                // Use unnormed scalars to calculate the overlapping segment
-                let overlap_src = r * <P::S>::from(0.0).max(osn_src / ssn_dst) + self.src();
-                let overlap_dst = r * <P::S>::from(1.0).min(osn_dst / ssn_dst) + self.src();
-
-                // We only return a line if a given threshold is met
-                if !overlap_src.abs_diff_eq(overlap_dst, tolerance) {
-                    return Line2DIntersection::CollinearOverlap(Line([overlap_src, overlap_dst]));
-                }
+            if collinear {
+                return self.collinear_touch(o);
             }
         }
 
         // Simple intersections
         // t for self, u for other
-        let t = (o.src() - self.src()).wedge(s) / det;
-        let u = (o.src() - self.src()).wedge(r) / det;
+        let t = exact_scalar::<P>(
+            (o.src() - self.src()).wedge(s) / det,
+            [s_src, s_dst],
+            self.0.map(|p| (p - o.src()).wedge(s)),
+        );
+        let u = exact_scalar::<P>(
+            (o.src() - self.src()).wedge(r) / det,
+            [o_src, o_dst],
+            o.0.map(|p| (p - self.src()).wedge(r)),
+        );
+        // Endpoints exactly on the other line are the intersection themselves
+        let pos = [
+            (s_src, self.src()),
+            (s_dst, self.dst()),
+            (o_src, o.src()),
+            (o_dst, o.dst()),
+        ]
+        .into_iter()
+        .find(|(side, _)| *side == Orientation::Degenerate)
+        .map_or_else(|| r * t + self.src(), |(_, p)| p);
         Line2DIntersection::Simple(
-            r * t + self.src(),
+            pos,
             Line2DIntersectionKind::new(t, tolerance),
             Line2DIntersectionKind::new(u, tolerance),
         )
     }
 }
 
+/// Snaps the scalar of an intersection along a line to the exact sides of its endpoints relative
+/// to the other line: endpoints on the other line are hit exactly, endpoints on opposite sides
+/// enclose the intersection and endpoints on the same side don't. In the latter case the
+/// intersection lies beyond the endpoint with the smaller `offset` from the other line.
+#[inline]
+fn exact_scalar<P: Point>(
+    scalar: P::S,
+    [src, dst]: [Orientation; 2],
+    [src_offset, dst_offset]: [P::S; 2],
+) -> P::S {
+    let [zero, one] = [<P::S>::from(0.0), <P::S>::from(1.0)];
+    match (src, dst) {
+        (Orientation::Degenerate, _) => zero,
+        (_, Orientation::Degenerate) => one,
+        (src, dst) if src != dst => scalar.max(zero).min(one),
+        _ if scalar < zero || scalar > one => scalar,
+        _ if src_offset.abs() <= dst_offset.abs() => -<P::S>::epsilon(),
+        _ => one + <P::S>::epsilon(),
+    }
+}
+
+impl<P: Point3> Line<P> {
+    /// The closest points of two line segments in 3D, the first one on `self`, the second one on
+    /// `o`. If there are several pairs of closest points, e.g. for overlapping parallel segments,
+    /// any of them is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use selo::prelude::*;
+    /// let beam = Line([Vec3::new(0.0, 0.0, 3.0), Vec3::new(4.0, 0.0, 3.0)]);
+    /// let column = Line([Vec3::new(2.0, 1.0, 0.0), Vec3::new(2.0, 1.0, 2.0)]);
+    ///
+    /// let [on_beam, on_column] = beam.closest_points(&column);
+    ///
+    /// assert_eq!(on_beam, Vec3::new(2.0, 0.0, 3.0));
+    /// assert_eq!(on_column, Vec3::new(2.0, 1.0, 2.0));
+    /// assert_eq!(beam.segment_distance(&column), 2f32.sqrt());
+    /// // the infinite lines pass each other at a distance of 1
+    /// assert_eq!(beam.skew_distance(&column), 1.0);
+    /// ```
+    #[inline]
+    pub fn closest_points(&self, o: &Self) -> [P; 2] {
+        let [t, u] = self.closest_scalars(o);
+        [self.pos_scaled(t), o.pos_scaled(u)]
+    }
+
+    /// The distance between the closest points of two line segments, see [`Line::closest_points`]
+    #[inline]
+    pub fn segment_distance(&self, o: &Self) -> P::S {
+        let [a, b] = self.closest_points(o);
+        (b - a).norm()
+    }
+
+    /// The distance between the infinite lines through two line segments. For parallel lines this
+    /// is their constant distance, otherwise the length of their common perpendicular.
+    #[inline]
+    pub fn skew_distance(&self, o: &Self) -> P::S {
+        match self.line_scalars(o) {
+            Some([t, u]) => (o.pos_scaled(u) - self.pos_scaled(t)).norm(),
+            None => (o.src() - self.project(o.src())).norm(),
+        }
+    }
+
+    /// Checks whether and where two line segments in 3D intersect. If the infinite lines through
+    /// the segments pass each other closer than `tolerance`, they are considered to lie in a
+    /// common plane and the result is the same as in 2D, see [`Line::intersection`]. The point of
+    /// a simple intersection is the midpoint between the closest points of the lines then.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use selo::prelude::*;
+    /// let edge = Line([Vec3::ZERO, Vec3::new(2.0, 2.0, 2.0)]);
+    /// let brace = Line([Vec3::new(0.0, 2.0, 0.0), Vec3::new(2.0, 0.0, 2.0)]);
+    ///
+    /// let Line3DIntersection::Coplanar(intersection) = edge.intersection_3d(&brace, 0.001) else {
+    ///     panic!("the lines cross each other");
+    /// };
+    /// assert_eq!(intersection.pos(), Some(Vec3::ONE));
+    ///
+    /// let lifted = Line(brace.0.map(|p| p + Vec3::new(0.5, -0.5, 0.0)));
+    /// assert!(!edge.intersection_3d(&lifted, 0.001).intersect());
+    /// ```
+    #[inline]
+    pub fn intersection_3d(&self, o: &Self, tolerance: P::S) -> Line3DIntersection<P> {
+        let r = self.to_dst();
+        let tolerance_relaxed = tolerance * <P::S>::from(10.0);
+
+        let Some([t, u]) = self
+            .line_scalars(o)
+            .filter(|_| self.sine_length(o) > tolerance_relaxed)
+        else {
+            // parallel lines always share a plane
+            let v = o.src() - self.src();
+            let cp = (v.dot(v) * r.dot(r) - v.dot(r) * v.dot(r))
+                .max(<P::S>::from(0.0))
+                .sqrt();
+            if cp > tolerance_relaxed {
+                return Line3DIntersection::Coplanar(Line2DIntersection::ParallelNonCollinear);
+            }
+            return Line3DIntersection::Coplanar(
+                self.collinear_intersection(o, tolerance)
+                    .unwrap_or_else(|| self.collinear_touch(o)),
+            );
+        };
+
+        let (a, b) = (self.pos_scaled(t), o.pos_scaled(u));
+        if (b - a).norm() > tolerance {
+            return Line3DIntersection::Skew(Line(self.closest_points(o)));
+        }
+        Line3DIntersection::Coplanar(Line2DIntersection::Simple(
+            Line([a, b]).center(),
+            Line2DIntersectionKind::new(t, tolerance),
+            Line2DIntersectionKind::new(u, tolerance),
+        ))
+    }
+
+    /// The length of the cross product of both directions, i.e. the 3D equivalent of the
+    /// determinant in [`Line::intersection`]
+    #[inline]
+    fn sine_length(&self, o: &Self) -> P::S {
+        let (r, s) = (self.to_dst(), o.to_dst());
+        (r.dot(r) * s.dot(s) - r.dot(s) * r.dot(s))
+            .max(<P::S>::from(0.0))
+            .sqrt()
+    }
+
+    /// The scalars of the closest points of the infinite lines, `None` if they are parallel
+    #[inline]
+    fn line_scalars(&self, o: &Self) -> Option<[P::S; 2]> {
+        let (r, s, w) = (self.to_dst(), o.to_dst(), self.src() - o.src());
+        let (a, b, c) = (r.dot(r), r.dot(s), s.dot(s));
+        let (d, e) = (r.dot(w), s.dot(w));
+        let denominator = a * c - b * b;
+        if denominator <= P::S::epsilon() * a * c {
+            return None;
+        }
+        Some([(b * e - c * d) / denominator, (a * e - b * d) / denominator])
+    }
+
+    /// The scalars of the closest points of the segments, after "Real-Time Collision Detection"
+    /// by Christer Ericson, section 5.1.9
+    #[inline]
+    fn closest_scalars(&self, o: &Self) -> [P::S; 2] {
+        let (zero, one) = (<P::S>::from(0.0), <P::S>::from(1.0));
+        let clamp = |x: P::S| x.max(zero).min(one);
+        let (r, s, w) = (self.to_dst(), o.to_dst(), self.src() - o.src());
+        let (a, e, f) = (r.dot(r), s.dot(s), s.dot(w));
+
+        if a <= P::S::epsilon() && e <= P::S::epsilon() {
+            return [zero, zero];
+        }
+        if a <= P::S::epsilon() {
+            return [zero, clamp(f / e)];
+        }
+        let c = r.dot(w);
+        if e <= P::S::epsilon() {
+            return [clamp(-c / a), zero];
+        }
+        let b = r.dot(s);
+        let denominator = a * e - b * b;
+        let t = if denominator > zero {
+            clamp((b * f - c * e) / denominator)
+        } else {
+            zero
+        };
+        let u = (b * t + f) / e;
+        if u < zero {
+            [clamp(-c / a), zero]
+        } else if u > one {
+            [clamp((b - c) / a), one]
+        } else {
+            [t, u]
+        }
+    }
+}
+
+/// Returns the result of a line segment intersection check in 3D, see [`Line::intersection_3d`].
+#[derive(Clone, Copy, Debug)]
+pub enum Line3DIntersection<P: Point> {
+    /// The lines lie in a common plane, the cases are the same as in 2D
+    Coplanar(Line2DIntersection<P>),
+    /// The lines pass each other without sharing a plane, with the shortest connection between
+    /// the segments
+    Skew(Line<P>),
+}
+impl<P: Point> Line3DIntersection<P> {
+    /// Returns true if both line segments intersect, see [`Line2DIntersection::intersect`]
+    #[inline]
+    pub fn intersect(&self) -> bool {
+        match self {
+            Self::Coplanar(intersection) => intersection.intersect(),
+            Self::Skew(_) => false,
+        }
+    }
+
+    /// Returns the position of the intersection - if any, see [`Line2DIntersection::pos`]
+    #[inline]
+    pub fn pos(&self) -> Option<P> {
+        match self {
+            Self::Coplanar(intersection) => intersection.pos(),
+            Self::Skew(_) => None,
+        }
+    }
+}
+
 /// Kind of a line segment intersection for one Line
 /// Resembles the position of a line intersection regarding a line segment.
 /// Scalar * (dst - src) = Pos of intersection.
 #[derive(Clone, Copy, Debug)]
-pub enum Line2DIntersectionKind<P: Point2> {
+pub enum Line2DIntersectionKind<P: Point> {
     Inside(P::S),
     OutsideSrc(P::S),
     OutsideDst(P::S),
 }
-impl<P: Point2> Line2DIntersectionKind<P> {
+impl<P: Point> Line2DIntersectionKind<P> {
     #[inline]
     fn new(scalar: P::S, tolerance: P::S) -> Self {
         if scalar < -tolerance {
@@ -122,7 +384,7 @@ impl<P: Point2> Line2DIntersectionKind<P> {
 
 /// Returns the result of a Line2D intersection check.
 #[derive(Clone, Copy, Debug)]
-pub enum Line2DIntersection<P: Point2> {
+pub enum Line2DIntersection<P: Point> {
     /// The given Line2D Segments overlap: parallel, collinear and share a given
     Simple(P, Line2DIntersectionKind<P>, Line2DIntersectionKind<P>),
     /// Lines are collinear and share the given Line.
@@ -134,7 +396,7 @@ pub enum Line2DIntersection<P: Point2> {
     /// Line2D segments are Parallel && !collinear
     ParallelNonCollinear,
 }
-impl<P: Point2> Line2DIntersection<P> {
+impl<P: Point> Line2DIntersection<P> {
     /// Returns true if both line segments have a true intersection.
     #[inline]
     pub fn intersect(&self) -> bool {
@@ -1,11 +1,38 @@
 pub mod grouping;
 pub use grouping::*;
 
+mod convex_decomposition;
+pub(crate) use convex_decomposition::*;
+
 mod line_intersection;
 pub use line_intersection::*;
 
 mod split;
 pub use split::*;
 
+mod plane_fit;
+pub(crate) use plane_fit::*;
+
+mod predicates;
+pub use predicates::*;
+
+mod rectangle_partition;
+pub(crate) use rectangle_partition::*;
+
+mod section;
+pub(crate) use section::*;
+
 mod simplify;
 pub use simplify::*;
+
+mod stitch;
+pub(crate) use stitch::*;
+
+mod sweep;
+pub use sweep::*;
+
+mod straight_skeleton;
+pub use straight_skeleton::*;
+
+mod triangulation;
+pub(crate) use triangulation::*;
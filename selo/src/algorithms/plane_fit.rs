@@ -0,0 +1,94 @@
+use bevy_math::{DMat3, DVec3};
+
+/// The least squares plane through a set of points as unit normal and centroid, together with the
+/// maximum and the root mean square distance of the points to it.
+///
+/// The normal is the direction in which the points spread the least, i.e. the eigenvector of the
+/// smallest eigenvalue of their covariance matrix. It's oriented to agree with the winding of the
+/// points, if they have one. A last point repeating the first one, like the closing point of a
+/// ring, is ignored so that it doesn't weigh twice. Returns `None` for fewer than three points or if
+/// the points are collinear.
+pub(crate) fn fit_plane(
+    points: impl IntoIterator<Item = DVec3>,
+) -> Option<(DVec3, DVec3, f64, f64)> {
+    let mut points = points.into_iter().collect::<Vec<_>>();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 3 {
+        return None;
+    }
+    let centroid = points.iter().sum::<DVec3>() / points.len() as f64;
+    let centered = points.iter().map(|p| *p - centroid).collect::<Vec<_>>();
+
+    let covariance = centered.iter().fold(DMat3::ZERO, |sum, p| {
+        sum + DMat3::from_cols(*p * p.x, *p * p.y, *p * p.z)
+    });
+    let (eigenvalues, eigenvectors) = symmetric_eigen(covariance);
+    let mut order = [0, 1, 2];
+    order.sort_by(|a, b| eigenvalues[*a].total_cmp(&eigenvalues[*b]));
+    let [_, middle, largest] = order.map(|i| eigenvalues[i]);
+    // the points spread only along a line
+    if middle.is_nan() || middle <= largest * 1e-12 {
+        return None;
+    }
+
+    let normal = eigenvectors.col(order[0]).normalize();
+    let winding = centered
+        .iter()
+        .zip(centered.iter().cycle().skip(1))
+        .map(|(a, b)| a.cross(*b))
+        .sum::<DVec3>();
+    let normal = if winding.dot(normal) < 0.0 {
+        -normal
+    } else {
+        normal
+    };
+
+    let (max, sum_squared) = centered
+        .iter()
+        .map(|p| p.dot(normal))
+        .fold((0.0f64, 0.0), |(max, sum), d| {
+            (max.max(d.abs()), sum + d * d)
+        });
+    Some((
+        normal,
+        centroid,
+        max,
+        (sum_squared / points.len() as f64).sqrt(),
+    ))
+}
+
+/// The eigenvalues and the eigenvectors, as columns, of a symmetric matrix by cyclic Jacobi
+/// rotations
+fn symmetric_eigen(mut a: DMat3) -> ([f64; 3], DMat3) {
+    let mut v = DMat3::IDENTITY;
+    let get = |m: &DMat3, row: usize, col: usize| m.col(col)[row];
+    let scale = a.to_cols_array().iter().map(|x| x * x).sum::<f64>();
+    for _ in 0..32 {
+        let off_diagonal = get(&a, 0, 1).powi(2) + get(&a, 0, 2).powi(2) + get(&a, 1, 2).powi(2);
+        if off_diagonal <= scale * f64::EPSILON * f64::EPSILON {
+            break;
+        }
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            let apq = get(&a, p, q);
+            if apq == 0.0 {
+                continue;
+            }
+            // the rotation which zeroes the element at (p, q)
+            let theta = (get(&a, q, q) - get(&a, p, p)) / (2.0 * apq);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            let mut cols = DMat3::IDENTITY.to_cols_array_2d();
+            cols[p][p] = c;
+            cols[p][q] = -s;
+            cols[q][p] = s;
+            cols[q][q] = c;
+            let rotation = DMat3::from_cols_array_2d(&cols);
+            a = rotation.transpose() * a * rotation;
+            v *= rotation;
+        }
+    }
+    ([get(&a, 0, 0), get(&a, 1, 1), get(&a, 2, 2)], v)
+}
@@ -0,0 +1,252 @@
+use num_traits::Float;
+use robust::{Coord, Coord3D};
+
+use crate::{Point2, Point3};
+
+/// The exact orientation of three points in 2D, or of a point relative to the plane through three
+/// points in 3D, see [`orient2d`] and [`orient3d`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+    /// The points are collinear in 2D or coplanar in 3D
+    Degenerate,
+}
+
+impl Orientation {
+    fn from_determinant(determinant: f64) -> Self {
+        if determinant > 0.0 {
+            Self::CounterClockwise
+        } else if determinant < 0.0 {
+            Self::Clockwise
+        } else {
+            Self::Degenerate
+        }
+    }
+
+    /// The orientation of the same points in reverse order
+    #[inline]
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::CounterClockwise => Self::Clockwise,
+            Self::Clockwise => Self::CounterClockwise,
+            Self::Degenerate => Self::Degenerate,
+        }
+    }
+}
+
+/// Where a point lies relative to a circle, see [`incircle`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CircleSide {
+    Inside,
+    On,
+    Outside,
+}
+
+fn coord<P: Point2>(p: P) -> Coord<f64> {
+    Coord {
+        x: p.x().into(),
+        y: p.y().into(),
+    }
+}
+
+fn coord3d<P: Point3>(p: P) -> Coord3D<f64> {
+    Coord3D {
+        x: p.x().into(),
+        y: p.y().into(),
+        z: p.z().into(),
+    }
+}
+
+/// Whether `c` lies left of (counter-clockwise), right of (clockwise) or on the line through `a`
+/// and `b`.
+///
+/// The result is exact for all finite inputs, the predicate uses adaptive precision arithmetic
+/// and only falls back to slower exact arithmetic if the points are nearly collinear.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let (a, b) = (DVec2::new(0.5, 0.5), DVec2::new(12.0, 12.0));
+/// // the next smaller double below 24.0 lies slightly right of the diagonal
+/// let c = DVec2::new(24.0, f64::from_bits(24f64.to_bits() - 1));
+///
+/// assert_eq!(orient2d(a, b, c), Orientation::Clockwise);
+/// assert_eq!(orient2d(a, b, DVec2::splat(24.0)), Orientation::Degenerate);
+/// assert_eq!(orient2d(a, b, DVec2::Y), Orientation::CounterClockwise);
+/// ```
+#[inline]
+pub fn orient2d<P: Point2>(a: P, b: P, c: P) -> Orientation {
+    Orientation::from_determinant(robust::orient2d(coord(a), coord(b), coord(c)))
+}
+
+/// Whether `a`, `b` and `c` appear counter-clockwise or clockwise when looking at them from `d`,
+/// or whether all four points lie in a common plane. `d` lies on the side the right-handed normal
+/// of the triangle points to if they appear counter-clockwise.
+///
+/// The result is exact for all finite inputs, see [`orient2d`].
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let [a, b, c] = [DVec3::ZERO, DVec3::X, DVec3::Y];
+///
+/// assert_eq!(orient3d(a, b, c, DVec3::Z), Orientation::CounterClockwise);
+/// assert_eq!(orient3d(a, b, c, DVec3::NEG_Z), Orientation::Clockwise);
+/// assert_eq!(orient3d(a, b, c, DVec3::ONE.with_z(0.0)), Orientation::Degenerate);
+/// ```
+#[inline]
+pub fn orient3d<P: Point3>(a: P, b: P, c: P, d: P) -> Orientation {
+    // the robust crate considers the points to be counter-clockwise when seen from the other side
+    Orientation::from_determinant(-robust::orient3d(
+        coord3d(a),
+        coord3d(b),
+        coord3d(c),
+        coord3d(d),
+    ))
+}
+
+/// Where `d` lies relative to the circle through `a`, `b` and `c`, regardless of their
+/// orientation. Returns `None` if `a`, `b` and `c` are collinear, i.e. there's no such circle.
+///
+/// The result is exact for all finite inputs, see [`orient2d`].
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let [a, b, c] = [DVec2::X, DVec2::Y, DVec2::NEG_X];
+///
+/// assert_eq!(incircle(a, b, c, DVec2::ZERO), Some(CircleSide::Inside));
+/// assert_eq!(incircle(c, b, a, DVec2::NEG_Y), Some(CircleSide::On));
+/// assert_eq!(incircle(a, b, c, DVec2::ONE), Some(CircleSide::Outside));
+/// assert_eq!(incircle(a, DVec2::ZERO, c, DVec2::Y), None);
+/// ```
+#[inline]
+pub fn incircle<P: Point2>(a: P, b: P, c: P, d: P) -> Option<CircleSide> {
+    let sign = match orient2d(a, b, c) {
+        Orientation::CounterClockwise => 1.0,
+        Orientation::Clockwise => -1.0,
+        Orientation::Degenerate => return None,
+    };
+    let determinant = sign * robust::incircle(coord(a), coord(b), coord(c), coord(d));
+    Some(if determinant > 0.0 {
+        CircleSide::Inside
+    } else if determinant < 0.0 {
+        CircleSide::Outside
+    } else {
+        CircleSide::On
+    })
+}
+
+/// Whether `p` lies exactly on the line segment from `a` to `b`
+#[inline]
+pub(crate) fn on_segment<P: Point2>(a: P, b: P, p: P) -> bool {
+    orient2d(a, b, p) == Orientation::Degenerate && within_bounds(a, b, p)
+}
+
+/// Whether `p` lies within the axis aligned bounding box of `a` and `b`
+#[inline]
+fn within_bounds<P: Point2>(a: P, b: P, p: P) -> bool {
+    let within = |s: P::S, t: P::S, u: P::S| s.min(t) <= u && u <= s.max(t);
+    within(a.x(), b.x(), p.x()) && within(a.y(), b.y(), p.y())
+}
+
+/// The winding number of a closed ring of points around `p`, or `None` if `p` lies on the ring.
+/// The ring must not repeat its first point at the end.
+///
+/// Counts the signed crossings of the ring with the ray from `p` in positive x direction, where
+/// the side of `p` relative to each crossing edge is decided by [`orient2d`]. This makes the
+/// result exact, also for points extremely close to the boundary.
+pub(crate) fn winding_number<P: Point2>(ring: &[P], p: P) -> Option<i32> {
+    let mut winding = 0;
+    for (i, &a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        let orientation = orient2d(a, b, p);
+        if orientation == Orientation::Degenerate && within_bounds(a, b, p) {
+            return None;
+        }
+        if a.y() <= p.y() {
+            if b.y() > p.y() && orientation == Orientation::CounterClockwise {
+                winding += 1;
+            }
+        } else if b.y() <= p.y() && orientation == Orientation::Clockwise {
+            winding -= 1;
+        }
+    }
+    Some(winding)
+}
+
+#[cfg(test)]
+mod predicates_tests {
+    use bevy_math::{DVec2, DVec3, Vec2};
+
+    use super::*;
+
+    #[test]
+    fn nearly_collinear_grid() {
+        // the classic example of Kettner et al.: naive floating point orientation tests around
+        // the diagonal give inconsistent results in a tiny grid of representable points
+        let (b, c) = (DVec2::new(12.0, 12.0), DVec2::new(24.0, 24.0));
+        let step = f64::EPSILON / 2.0;
+        for i in 0..64 {
+            for j in 0..64 {
+                let a = DVec2::new(0.5 + i as f64 * step, 0.5 + j as f64 * step);
+                let expected = match j.cmp(&i) {
+                    std::cmp::Ordering::Greater => Orientation::CounterClockwise,
+                    std::cmp::Ordering::Less => Orientation::Clockwise,
+                    std::cmp::Ordering::Equal => Orientation::Degenerate,
+                };
+                // a lies above the diagonal for j > i, i.e. left of the line from b to c
+                assert_eq!(orient2d(b, c, a), expected, "{i} {j}");
+                assert_eq!(orient2d(c, b, a), expected.reverse());
+            }
+        }
+    }
+
+    #[test]
+    fn single_precision_points() {
+        let [a, b] = [Vec2::ZERO, Vec2::new(3.0, 1.0)];
+        let c = Vec2::new(3.0 * 1e7, 1e7);
+        assert_eq!(orient2d(a, b, c), Orientation::Degenerate);
+        let above = Vec2::new(c.x, f32::from_bits(c.y.to_bits() + 1));
+        assert_eq!(orient2d(a, b, above), Orientation::CounterClockwise);
+    }
+
+    #[test]
+    fn nearly_coplanar_points() {
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        let [a, b, c] = [DVec3::ZERO, DVec3::X, DVec3::Y].map(|p| p + offset);
+        let d = DVec3::new(0.3, 0.7, 0.0) + offset;
+        assert_eq!(orient3d(a, b, c, d), Orientation::Degenerate);
+        let above = d.with_z(f64::from_bits(d.z.to_bits() + 1));
+        assert_eq!(orient3d(a, b, c, above), Orientation::CounterClockwise);
+        assert_eq!(orient3d(a, c, b, above), Orientation::Clockwise);
+    }
+
+    #[test]
+    fn winding_around_points() {
+        let square = [DVec2::ZERO, DVec2::X, DVec2::ONE, DVec2::Y];
+        assert_eq!(winding_number(&square, DVec2::splat(0.5)), Some(1));
+        assert_eq!(winding_number(&square, DVec2::new(1.5, 0.5)), Some(0));
+        assert_eq!(winding_number(&square, DVec2::new(1.0, 0.5)), None);
+        assert_eq!(winding_number(&square, DVec2::Y), None);
+        // the ray passes through a vertex
+        assert_eq!(winding_number(&square, DVec2::new(-0.5, 1.0)), Some(0));
+
+        let mut clockwise = square;
+        clockwise.reverse();
+        assert_eq!(winding_number(&clockwise, DVec2::splat(0.5)), Some(-1));
+
+        // just inside the long, slightly tilted edge of a sliver
+        let sliver = [DVec2::ZERO, DVec2::new(1e6, 1.0), DVec2::new(0.0, 1.0)];
+        let edge_point = DVec2::new(5e5, 0.5);
+        assert_eq!(winding_number(&sliver, edge_point), None);
+        let inside = DVec2::new(5e5, f64::from_bits(0.5f64.to_bits() + 1));
+        assert_eq!(winding_number(&sliver, inside), Some(1));
+        let outside = DVec2::new(5e5, f64::from_bits(0.5f64.to_bits() - 1));
+        assert_eq!(winding_number(&sliver, outside), Some(0));
+    }
+}
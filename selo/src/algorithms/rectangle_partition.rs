@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+
+use bevy_math::DVec2;
+
+use crate::{errors::GeometryError, Polygon, Ring};
+
+use super::trace_boundary;
+
+/// A reflex vertex of a rectilinear polygon and the two directions in which the interior can be
+/// split at it
+#[derive(Debug, Clone, Copy)]
+struct Reflex {
+    position: DVec2,
+    directions: [DVec2; 2],
+}
+
+/// Partitions a rectilinear polygon into the minimal number of rectangles.
+///
+/// Chords between two reflex vertices each resolve two reflex vertices at once. The largest set of
+/// non-intersecting chords is found as maximal independent set of the bipartite intersection graph
+/// of the horizontal and vertical chords. All other reflex vertices are resolved by extending one
+/// of their edges until it hits the boundary or another cut.
+pub(crate) fn rectangle_partition(
+    polygon: &Polygon<DVec2>,
+) -> Result<Vec<Ring<DVec2>>, GeometryError> {
+    let rings = polygon
+        .iter_rings()
+        .enumerate()
+        .map(|(i, ring)| rectilinear_ring(ring.points_open(), i == 0))
+        .collect::<Result<Vec<_>, _>>()?;
+    let rings = rings
+        .into_iter()
+        .filter(|ring| !ring.is_empty())
+        .collect::<Vec<_>>();
+    if rings.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let boundary = rings
+        .iter()
+        .flat_map(|ring| {
+            ring.iter()
+                .zip(ring.iter().cycle().skip(1))
+                .map(|(a, b)| [*a, *b])
+        })
+        .collect::<Vec<_>>();
+    let reflex = rings
+        .iter()
+        .flat_map(|ring| {
+            let n = ring.len();
+            (0..n).filter_map(move |i| {
+                let [prev, p, next] = [(i + n - 1) % n, i, (i + 1) % n].map(|j| ring[j]);
+                ((p - prev).perp_dot(next - p) < 0.0).then(|| Reflex {
+                    position: p,
+                    directions: [(p - prev).normalize(), (p - next).normalize()],
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let (horizontal, vertical): (Vec<_>, Vec<_>) = good_chords(&reflex, &boundary)
+        .into_iter()
+        .partition(|[a, b]| reflex[*a].position.y == reflex[*b].position.y);
+    let chosen = independent_chords(&reflex, &horizontal, &vertical);
+
+    let mut resolved = vec![false; reflex.len()];
+    let mut cuts = vec![];
+    for [a, b] in chosen {
+        resolved[a] = true;
+        resolved[b] = true;
+        cuts.push([reflex[a].position, reflex[b].position]);
+    }
+    for i in 0..reflex.len() {
+        if resolved[i] {
+            continue;
+        }
+        resolved[i] = true;
+        let direction = reflex[i].directions[0];
+        let Some(end) = cast(reflex[i].position, direction, boundary.iter().chain(&cuts)) else {
+            return Err(GeometryError::InvalidGeometry);
+        };
+        // the cut may end in another reflex vertex which is resolved by it as well
+        for (j, other) in reflex.iter().enumerate() {
+            if other.position == end && other.directions.contains(&-direction) {
+                resolved[j] = true;
+            }
+        }
+        cuts.push([reflex[i].position, end]);
+    }
+
+    Ok(faces(&boundary, &cuts)
+        .into_iter()
+        .map(|face| Ring::new(without_collinear(face)))
+        .collect())
+}
+
+/// Removes collinear vertices, orients the ring and checks that all edges are axis-aligned
+fn rectilinear_ring(points: &[DVec2], exterior: bool) -> Result<Vec<DVec2>, GeometryError> {
+    let mut ring = without_collinear(points.to_vec());
+    if ring.len() < 3 {
+        return Ok(vec![]);
+    }
+    let axis_aligned = ring
+        .iter()
+        .zip(ring.iter().cycle().skip(1))
+        .all(|(a, b)| a.x == b.x || a.y == b.y);
+    if !axis_aligned || !ring.iter().all(|p| p.is_finite()) {
+        return Err(GeometryError::InvalidGeometry);
+    }
+    let area = ring
+        .iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum::<f64>();
+    if (area > 0.0) != exterior {
+        ring.reverse();
+    }
+    Ok(ring)
+}
+
+fn without_collinear(mut points: Vec<DVec2>) -> Vec<DVec2> {
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    loop {
+        let n = points.len();
+        if n < 3 {
+            return points;
+        }
+        let Some(i) = (0..n).find(|i| {
+            let [prev, p, next] = [(i + n - 1) % n, *i, (i + 1) % n].map(|j| points[j]);
+            (p - prev).perp_dot(next - p) == 0.0
+        }) else {
+            return points;
+        };
+        points.remove(i);
+    }
+}
+
+/// The first point hit by a ray along an axis, ignoring the origin itself
+fn cast<'a>(
+    origin: DVec2,
+    direction: DVec2,
+    segments: impl IntoIterator<Item = &'a [DVec2; 2]>,
+) -> Option<DVec2> {
+    let mut best: Option<(f64, DVec2)> = None;
+    for [a, b] in segments {
+        let candidates = if direction.x != 0.0 {
+            if a.x == b.x && a.y.min(b.y) <= origin.y && origin.y <= a.y.max(b.y) {
+                vec![DVec2::new(a.x, origin.y)]
+            } else if a.y == origin.y && b.y == origin.y {
+                vec![*a, *b]
+            } else {
+                vec![]
+            }
+        } else if a.y == b.y && a.x.min(b.x) <= origin.x && origin.x <= a.x.max(b.x) {
+            vec![DVec2::new(origin.x, a.y)]
+        } else if a.x == origin.x && b.x == origin.x {
+            vec![*a, *b]
+        } else {
+            vec![]
+        };
+        for hit in candidates {
+            let t = (hit - origin).dot(direction);
+            if t > 0.0 && best.is_none_or(|(best, _)| t < best) {
+                best = Some((t, hit));
+            }
+        }
+    }
+    best.map(|(_, hit)| hit)
+}
+
+/// Pairs of reflex vertices which can be connected by an axis-aligned cut that splits both of them
+fn good_chords(reflex: &[Reflex], boundary: &[[DVec2; 2]]) -> Vec<[usize; 2]> {
+    let mut chords = vec![];
+    for (i, a) in reflex.iter().enumerate() {
+        for direction in a.directions {
+            let Some(hit) = cast(a.position, direction, boundary) else {
+                continue;
+            };
+            let partner = reflex
+                .iter()
+                .position(|b| b.position == hit && b.directions.contains(&-direction));
+            if let Some(j) = partner.filter(|j| i < *j) {
+                chords.push([i, j]);
+            }
+        }
+    }
+    chords
+}
+
+/// The largest set of chords which don't touch each other, by Kőnig's theorem the complement of
+/// a minimum vertex cover of the intersection graph
+fn independent_chords(
+    reflex: &[Reflex],
+    horizontal: &[[usize; 2]],
+    vertical: &[[usize; 2]],
+) -> Vec<[usize; 2]> {
+    let intersects = |[a, b]: [usize; 2], [c, d]: [usize; 2]| {
+        let [a, b, c, d] = [a, b, c, d].map(|i| reflex[i].position);
+        a.x.min(b.x) <= c.x && c.x <= a.x.max(b.x) && c.y.min(d.y) <= a.y && a.y <= c.y.max(d.y)
+    };
+    let adjacent = horizontal
+        .iter()
+        .map(|h| {
+            (0..vertical.len())
+                .filter(|v| intersects(*h, vertical[*v]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // maximum matching with augmenting paths
+    let mut match_of_vertical: Vec<Option<usize>> = vec![None; vertical.len()];
+    fn augment(
+        h: usize,
+        adjacent: &[Vec<usize>],
+        visited: &mut [bool],
+        match_of_vertical: &mut [Option<usize>],
+    ) -> bool {
+        for &v in &adjacent[h] {
+            if visited[v] {
+                continue;
+            }
+            visited[v] = true;
+            if match_of_vertical[v]
+                .is_none_or(|other| augment(other, adjacent, visited, match_of_vertical))
+            {
+                match_of_vertical[v] = Some(h);
+                return true;
+            }
+        }
+        false
+    }
+    for h in 0..horizontal.len() {
+        augment(
+            h,
+            &adjacent,
+            &mut vec![false; vertical.len()],
+            &mut match_of_vertical,
+        );
+    }
+
+    // alternating paths from unmatched horizontal chords
+    let mut matched_horizontal = vec![false; horizontal.len()];
+    for h in match_of_vertical.iter().flatten() {
+        matched_horizontal[*h] = true;
+    }
+    let mut reached_horizontal = matched_horizontal.iter().map(|m| !m).collect::<Vec<_>>();
+    let mut reached_vertical = vec![false; vertical.len()];
+    let mut stack = (0..horizontal.len())
+        .filter(|h| reached_horizontal[*h])
+        .collect::<Vec<_>>();
+    while let Some(h) = stack.pop() {
+        for &v in &adjacent[h] {
+            if reached_vertical[v] {
+                continue;
+            }
+            reached_vertical[v] = true;
+            if let Some(next) = match_of_vertical[v].filter(|next| !reached_horizontal[*next]) {
+                reached_horizontal[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+
+    horizontal
+        .iter()
+        .zip(reached_horizontal)
+        .filter(|(_, reached)| *reached)
+        .map(|(chord, _)| *chord)
+        .chain(
+            vertical
+                .iter()
+                .zip(reached_vertical)
+                .filter(|(_, reached)| !*reached)
+                .map(|(chord, _)| *chord),
+        )
+        .collect()
+}
+
+/// The faces of the boundary subdivided by the cuts
+fn faces(boundary: &[[DVec2; 2]], cuts: &[[DVec2; 2]]) -> Vec<Vec<DVec2>> {
+    let mut vertices = vec![];
+    let mut index_of = HashMap::new();
+    let mut index = |p: DVec2| {
+        *index_of
+            .entry((p.x.to_bits(), p.y.to_bits()))
+            .or_insert_with(|| {
+                vertices.push(p);
+                vertices.len() - 1
+            })
+    };
+    let directed = boundary
+        .iter()
+        .copied()
+        .chain(cuts.iter().flat_map(|[a, b]| [[*a, *b], [*b, *a]]))
+        .map(|[a, b]| [index(a), index(b)])
+        .collect::<Vec<_>>();
+
+    // cuts end on other edges, which have to be split there
+    let mut outgoing = HashMap::<usize, Vec<usize>>::new();
+    for [a, b] in directed {
+        let (src, dst) = (vertices[a], vertices[b]);
+        let mut inner = (0..vertices.len())
+            .filter(|i| {
+                let p = vertices[*i];
+                let t = (p - src).dot(dst - src);
+                (p - src).perp_dot(dst - src) == 0.0 && t > 0.0 && t < src.distance_squared(dst)
+            })
+            .collect::<Vec<_>>();
+        inner.sort_by(|i, j| {
+            src.distance_squared(vertices[*i])
+                .total_cmp(&src.distance_squared(vertices[*j]))
+        });
+        let path = std::iter::once(a)
+            .chain(inner)
+            .chain(std::iter::once(b))
+            .collect::<Vec<_>>();
+        for pair in path.windows(2) {
+            outgoing.entry(pair[0]).or_default().push(pair[1]);
+        }
+    }
+    trace_boundary(&vertices, outgoing)
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use bevy_math::{DAffine3, DVec2, DVec3};
+
+/// The lines in which a set of faces intersects a plane, in the 2D coordinates of the plane. Each
+/// face is given by its normal and its rings.
+///
+/// Vertices exactly on the plane are treated as being above it, so a closed surface always results
+/// in closed rings and faces lying in the plane don't contribute anything. Each chord is oriented
+/// such that the side the face normal points away from is on its left, i.e. the rings of closed
+/// surfaces with outward normals run counter-clockwise around the material.
+pub(crate) fn section<'a>(
+    faces: impl IntoIterator<Item = (DVec3, Vec<&'a [DVec3]>)>,
+    normal: DVec3,
+    origin: DVec3,
+    projection: DAffine3,
+) -> (Vec<Vec<DVec2>>, Vec<Vec<DVec2>>) {
+    let segments = faces
+        .into_iter()
+        .flat_map(|(face_normal, rings)| chords(normal, origin, face_normal, &rings))
+        .map(|chord| chord.map(|p| projection.transform_point3(p).truncate()))
+        .filter(|[a, b]| a != b)
+        .collect::<Vec<_>>();
+    chain(&segments)
+}
+
+/// The segments in which a face, given by its normal and rings, intersects a plane. See
+/// [`section`] for the handling of vertices on the plane and the orientation of the segments.
+pub(crate) fn chords(
+    normal: DVec3,
+    origin: DVec3,
+    face_normal: DVec3,
+    rings: &[&[DVec3]],
+) -> Vec<[DVec3; 2]> {
+    let distance = |p: DVec3| normal.dot(p - origin);
+    let mut hits = vec![];
+    for ring in rings {
+        for (a, b) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+            // shared edges of neighbouring faces have to result in the exact same point
+            let (a, b) = if a.to_array() < b.to_array() {
+                (*a, *b)
+            } else {
+                (*b, *a)
+            };
+            let (da, db) = (distance(a), distance(b));
+            if (da >= 0.0) != (db >= 0.0) {
+                hits.push(a + (b - a) * (da / (da - db)));
+            }
+        }
+    }
+    if hits.len() < 2 {
+        return vec![];
+    }
+
+    let direction = normal.cross(face_normal);
+    let direction = if direction.length_squared() > 0.0 {
+        direction
+    } else {
+        hits[hits.len() - 1] - hits[0]
+    };
+    hits.sort_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)));
+    hits.chunks_exact(2)
+        .map(|pair| [pair[0], pair[1]])
+        .collect()
+}
+
+fn key(p: DVec2) -> (u64, u64) {
+    // adding zero turns negative zeros into positive ones
+    ((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits())
+}
+
+/// Chains segments with common endpoints into closed rings and open linestrings. The chains keep
+/// the direction most of their segments agree with.
+fn chain(segments: &[[DVec2; 2]]) -> (Vec<Vec<DVec2>>, Vec<Vec<DVec2>>) {
+    let mut ends = HashMap::<_, Vec<(usize, usize)>>::new();
+    for (i, segment) in segments.iter().enumerate() {
+        for (end, p) in segment.iter().enumerate() {
+            ends.entry(key(*p)).or_default().push((i, end));
+        }
+    }
+
+    let mut used = vec![false; segments.len()];
+    // follows the segments from `p` as long as the chain doesn't branch, returns the points and
+    // by how many segments more were passed in their direction than against it
+    let extend = |mut p: DVec2, used: &mut [bool]| {
+        let (mut points, mut agreement) = (vec![], 0);
+        loop {
+            let mut next = ends[&key(p)].iter().filter(|(i, _)| !used[*i]);
+            let (Some(&(i, end)), None) = (next.next(), next.next()) else {
+                return (points, agreement);
+            };
+            used[i] = true;
+            agreement += if end == 0 { 1 } else { -1 };
+            p = segments[i][1 - end];
+            points.push(p);
+        }
+    };
+
+    let (mut rings, mut lines) = (vec![], vec![]);
+    for i in 0..segments.len() {
+        if used[i] {
+            continue;
+        }
+        used[i] = true;
+        let [start, end] = segments[i];
+        let (forward, forward_agreement) = extend(end, &mut used);
+        let (backward, backward_agreement) = extend(start, &mut used);
+
+        let mut points = backward
+            .into_iter()
+            .rev()
+            .chain([start, end])
+            .chain(forward)
+            .collect::<Vec<_>>();
+        if 1 + forward_agreement - backward_agreement < 0 {
+            points.reverse();
+        }
+        if points.len() > 3 && key(points[0]) == key(points[points.len() - 1]) {
+            points.pop();
+            rings.push(points);
+        } else {
+            lines.push(points);
+        }
+    }
+    (rings, lines)
+}
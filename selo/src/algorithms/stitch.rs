@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use bevy_math::DVec2;
+
+use crate::{Line, MultiPolygon, MultiRing, Polygon, Ring, Triangle};
+
+/// Merges vertices which are closer than the tolerance
+struct Welder {
+    tolerance: f64,
+    vertices: Vec<DVec2>,
+    grid: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl Welder {
+    fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            vertices: vec![],
+            grid: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, p: DVec2) -> (i64, i64) {
+        if self.tolerance > 0.0 {
+            (
+                (p.x / self.tolerance).floor() as i64,
+                (p.y / self.tolerance).floor() as i64,
+            )
+        } else {
+            // adding zero turns negative zeros into positive ones
+            ((p.x + 0.0).to_bits() as i64, (p.y + 0.0).to_bits() as i64)
+        }
+    }
+
+    fn insert(&mut self, p: DVec2) -> usize {
+        let (x, y) = self.cell(p);
+        let range = if self.tolerance > 0.0 { -1..=1 } else { 0..=0 };
+        let existing = range
+            .clone()
+            .flat_map(|dx| range.clone().map(move |dy| (x + dx, y + dy)))
+            .filter_map(|cell| self.grid.get(&cell))
+            .flatten()
+            .copied()
+            .find(|i| self.vertices[*i].distance(p) <= self.tolerance);
+        existing.unwrap_or_else(|| {
+            self.vertices.push(p);
+            let index = self.vertices.len() - 1;
+            self.grid.entry((x, y)).or_default().push(index);
+            index
+        })
+    }
+}
+
+fn orient(a: DVec2, b: DVec2, c: DVec2) -> f64 {
+    (b - a).perp_dot(c - a)
+}
+
+/// Splits an edge at all vertices which lie on it, e.g. at T-junctions of the triangles
+fn split_edge(vertices: &[DVec2], tolerance: f64, a: usize, b: usize) -> Vec<(usize, usize)> {
+    let (src, dst) = (vertices[a], vertices[b]);
+    let mut inner = (0..vertices.len())
+        .filter(|i| *i != a && *i != b)
+        .filter_map(|i| {
+            let t = (vertices[i] - src).dot(dst - src) / (dst - src).length_squared();
+            let on_edge = t > 0.0 && t < 1.0 && src.lerp(dst, t).distance(vertices[i]) <= tolerance;
+            on_edge.then_some((t, i))
+        })
+        .collect::<Vec<_>>();
+    inner.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    std::iter::once(a)
+        .chain(inner.into_iter().map(|(_, i)| i))
+        .chain(std::iter::once(b))
+        .collect::<Vec<_>>()
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect()
+}
+
+/// Number of uses of an undirected edge in the direction from the smaller to the larger index and
+/// in the opposite direction
+fn count_edges(edges: &[(usize, usize)]) -> HashMap<(usize, usize), [usize; 2]> {
+    let mut counts = HashMap::<_, [usize; 2]>::new();
+    for (a, b) in edges {
+        counts.entry((*a.min(b), *a.max(b))).or_default()[usize::from(a > b)] += 1;
+    }
+    counts
+}
+
+/// Reconstructs the polygons covered by the triangles. Vertices within the tolerance are merged,
+/// edges which are used by more than one triangle in the same direction or by more than two
+/// triangles are returned as error.
+pub(crate) fn stitch_triangles(
+    triangles: &[Triangle<DVec2>],
+    tolerance: f64,
+) -> Result<MultiPolygon<DVec2>, Vec<Line<DVec2>>> {
+    let tolerance = if tolerance.is_finite() {
+        tolerance.max(0.0)
+    } else {
+        0.0
+    };
+
+    let mut welder = Welder::new(tolerance);
+    let mut edges = vec![];
+    for triangle in triangles
+        .iter()
+        .filter(|triangle| triangle.0.iter().all(|p| p.is_finite()))
+    {
+        let [a, b, c] = triangle.0.map(|p| welder.insert(p));
+        let [pa, pb, pc] = [a, b, c].map(|i| welder.vertices[i]);
+        let area = orient(pa, pb, pc);
+        if a == b || b == c || c == a || area == 0.0 {
+            continue;
+        }
+        let [a, b, c] = if area > 0.0 { [a, b, c] } else { [a, c, b] };
+        edges.extend([(a, b), (b, c), (c, a)]);
+    }
+    let vertices = welder.vertices;
+
+    // edges without a partner may still match several shorter edges
+    let counts = count_edges(&edges);
+    let edges = edges
+        .into_iter()
+        .flat_map(|(a, b)| {
+            if counts[&(a.min(b), a.max(b))].iter().sum::<usize>() == 1 {
+                split_edge(&vertices, tolerance, a, b)
+            } else {
+                vec![(a, b)]
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut non_manifold = vec![];
+    let mut outgoing = HashMap::<usize, Vec<usize>>::new();
+    let mut counts = count_edges(&edges).into_iter().collect::<Vec<_>>();
+    counts.sort_unstable();
+    for ((a, b), count) in counts {
+        match count {
+            [1, 0] => outgoing.entry(a).or_default().push(b),
+            [0, 1] => outgoing.entry(b).or_default().push(a),
+            [1, 1] => {}
+            _ => non_manifold.push(Line([vertices[a], vertices[b]])),
+        }
+    }
+    if !non_manifold.is_empty() {
+        return Err(non_manifold);
+    }
+
+    let rings = trace_boundary(&vertices, outgoing);
+    let (exteriors, holes): (Vec<_>, Vec<_>) = rings
+        .into_iter()
+        .map(|ring| (ring_area(&ring), ring))
+        .filter(|(area, _)| *area != 0.0)
+        .partition(|(area, _)| *area > 0.0);
+
+    let mut interiors = vec![vec![]; exteriors.len()];
+    for (_, hole) in holes {
+        let probe = hole[0].lerp(hole[1], 0.5);
+        let exterior = (0..exteriors.len())
+            .filter(|i| contains(&exteriors[*i].1, probe))
+            .min_by(|a, b| exteriors[*a].0.total_cmp(&exteriors[*b].0));
+        if let Some(exterior) = exterior {
+            interiors[exterior].push(Ring::new(hole));
+        }
+    }
+
+    Ok(MultiPolygon(
+        exteriors
+            .into_iter()
+            .zip(interiors)
+            .map(|((_, exterior), interiors)| {
+                Polygon::new(Ring::new(exterior), MultiRing(interiors))
+            })
+            .collect(),
+    ))
+}
+
+/// Follows the boundary edges, taking the sharpest left turn where several boundaries touch in a
+/// vertex so that every ring stays simple and encloses the area to the left of its edges. Going
+/// back along the same edge is only taken if there's no other way.
+pub(crate) fn trace_boundary(
+    vertices: &[DVec2],
+    mut outgoing: HashMap<usize, Vec<usize>>,
+) -> Vec<Vec<DVec2>> {
+    let mut starts = outgoing.keys().copied().collect::<Vec<_>>();
+    starts.sort_unstable();
+
+    let mut rings = vec![];
+    for start in starts {
+        while let Some(first) = outgoing.get_mut(&start).and_then(|next| next.pop()) {
+            let mut ring = vec![start];
+            let (mut previous, mut current) = (start, first);
+            while current != start {
+                ring.push(current);
+                let Some(next) = outgoing.get_mut(&current).filter(|next| !next.is_empty()) else {
+                    break;
+                };
+                let back = vertices[previous] - vertices[current];
+                let turn = |i: &usize| {
+                    let ahead = vertices[*i] - vertices[current];
+                    let angle = (-back.perp_dot(ahead))
+                        .atan2(back.dot(ahead))
+                        .rem_euclid(std::f64::consts::TAU);
+                    if angle == 0.0 {
+                        std::f64::consts::TAU
+                    } else {
+                        angle
+                    }
+                };
+                let (index, _) = next
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| turn(a).total_cmp(&turn(b)))
+                    .expect("not empty");
+                previous = current;
+                current = next.swap_remove(index);
+            }
+            rings.push(ring.into_iter().map(|i| vertices[i]).collect());
+        }
+    }
+    rings
+}
+
+fn ring_area(ring: &[DVec2]) -> f64 {
+    ring.iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum::<f64>()
+        * 0.5
+}
+
+fn contains(ring: &[DVec2], p: DVec2) -> bool {
+    ring.iter()
+        .zip(ring.iter().cycle().skip(1))
+        .filter(|(a, b)| (a.y > p.y) != (b.y > p.y))
+        .filter(|(a, b)| p.x < a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y))
+        .count()
+        % 2
+        == 1
+}
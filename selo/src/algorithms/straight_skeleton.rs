@@ -0,0 +1,714 @@
+use bevy_math::DVec2;
+
+use crate::{errors::GeometryError, Line, Orient2d, Point, Polygon, Ring};
+
+/// A node of a [`SkeletonGraph`], i.e. a point where the wavefront of the shrinking polygon changes
+/// its shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkeletonNode<P: Point> {
+    pub position: P,
+    /// The offset distance at which the wavefront reaches this node. This is `0` for the vertices
+    /// of the input polygon.
+    pub time: P::S,
+}
+
+/// An arc of a [`SkeletonGraph`] traced by a vertex of the wavefront.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkeletonArc {
+    /// The node where the arc starts and the node where it ends. The start is always reached first
+    /// by the wavefront.
+    pub nodes: [usize; 2],
+    /// The faces on the left and on the right of the arc, looking from its start to its end
+    pub faces: [usize; 2],
+}
+
+/// The area swept by a single edge of the input polygon while the wavefront shrinks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkeletonFace<P: Point> {
+    /// The edge of the input polygon which sweeps this face
+    pub edge: Line<P>,
+    /// The nodes on the boundary of the face in counter-clockwise order. The first two nodes are
+    /// the start and the end of `edge`.
+    pub nodes: Vec<usize>,
+}
+
+/// The straight skeleton of a polygon as a graph.
+///
+/// The first nodes are the vertices of the input polygon(s), ring by ring in the order of
+/// [`Polygon::iter_rings`] and oriented with [`Orient2d::orient_default`]. There is one face for
+/// every edge of the input, the `n`th face belongs to the edge starting at the `n`th node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkeletonGraph<P: Point> {
+    pub nodes: Vec<SkeletonNode<P>>,
+    pub arcs: Vec<SkeletonArc>,
+    pub faces: Vec<SkeletonFace<P>>,
+}
+
+impl<P: Point> Default for SkeletonGraph<P> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            arcs: vec![],
+            faces: vec![],
+        }
+    }
+}
+
+impl<P: Point> SkeletonGraph<P> {
+    /// The arcs of the skeleton as lines, without the edges of the input polygon
+    pub fn lines(&self) -> impl Iterator<Item = Line<P>> + '_ {
+        self.arcs
+            .iter()
+            .map(|arc| Line(arc.nodes.map(|node| self.nodes[node].position)))
+    }
+
+    /// The boundary of a face as a [`Ring`]
+    pub fn face_ring(&self, face: usize) -> Ring<P> {
+        Ring::new(
+            self.faces[face]
+                .nodes
+                .iter()
+                .map(|node| self.nodes[*node].position)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Appends the nodes, arcs and faces of another graph, shifting its indices
+    pub fn append(&mut self, mut other: Self) {
+        let (node_offset, face_offset) = (self.nodes.len(), self.faces.len());
+        self.nodes.append(&mut other.nodes);
+        self.arcs
+            .extend(other.arcs.into_iter().map(|arc| SkeletonArc {
+                nodes: arc.nodes.map(|node| node + node_offset),
+                faces: arc.faces.map(|face| face + face_offset),
+            }));
+        self.faces.extend(other.faces.into_iter().map(|face| {
+            SkeletonFace {
+                nodes: face
+                    .nodes
+                    .into_iter()
+                    .map(|node| node + node_offset)
+                    .collect(),
+                ..face
+            }
+        }));
+    }
+
+    /// Converts the positions and times of all nodes
+    pub fn map<Q: Point>(
+        self,
+        position: impl Fn(P) -> Q,
+        time: impl Fn(P::S) -> Q::S,
+    ) -> SkeletonGraph<Q> {
+        SkeletonGraph {
+            nodes: self
+                .nodes
+                .into_iter()
+                .map(|node| SkeletonNode {
+                    position: position(node.position),
+                    time: time(node.time),
+                })
+                .collect(),
+            arcs: self.arcs,
+            faces: self
+                .faces
+                .into_iter()
+                .map(|face| SkeletonFace {
+                    edge: Line(face.edge.0.map(&position)),
+                    nodes: face.nodes,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Computes the (weighted) straight skeleton of a polygon by simulating its shrinking wavefront.
+///
+/// Every edge moves inwards with the speed given by `weight`, which is called with the index of
+/// the edge. A speed of `0` keeps the edge in place. Fails with [`GeometryError::InvalidGeometry`]
+/// for rings with repeated consecutive points and with [`GeometryError::NotConverged`] if the
+/// simulation doesn't finish, which only happens for degenerate input.
+pub(crate) fn weighted_straight_skeleton(
+    polygon: &Polygon<DVec2>,
+    weight: impl Fn(usize) -> f64,
+) -> Result<SkeletonGraph<DVec2>, GeometryError> {
+    let polygon = polygon.orient_default();
+    let rings = polygon
+        .iter_rings()
+        .map(|ring| ring.points_open().to_vec())
+        .filter(|points| points.len() >= 3)
+        .collect::<Vec<_>>();
+
+    let mut wavefront = Wavefront::default();
+    let mut graph = SkeletonGraph::default();
+    for points in rings {
+        let offset = wavefront.edges.len();
+        let n = points.len();
+        for (i, p) in points.iter().enumerate() {
+            let next = points[(i + 1) % n];
+            let dir = (next - *p)
+                .try_normalize()
+                .ok_or(GeometryError::InvalidGeometry)?;
+            wavefront.edges.push(WavefrontEdge {
+                dir,
+                normal: dir.perp(),
+                weight: weight(offset + i).max(0.0),
+            });
+            graph.nodes.push(SkeletonNode {
+                position: *p,
+                time: 0.0,
+            });
+            graph.faces.push(SkeletonFace {
+                edge: Line([*p, next]),
+                nodes: vec![offset + i, offset + (i + 1) % n],
+            });
+        }
+        let vertices = (0..n)
+            .map(|i| {
+                let (left, right) = (offset + (i + n - 1) % n, offset + i);
+                Vertex {
+                    position: points[i],
+                    velocity: wavefront.velocity(left, right),
+                    node: offset + i,
+                    left,
+                    right,
+                }
+            })
+            .collect();
+        wavefront.loops.push(vertices);
+    }
+    if graph.nodes.is_empty() {
+        return Ok(graph);
+    }
+
+    let (min, max) = graph.nodes.iter().fold(
+        (DVec2::INFINITY, DVec2::NEG_INFINITY),
+        |(min, max), node| (min.min(node.position), max.max(node.position)),
+    );
+    wavefront.eps = (max - min).length() * 1e-9;
+    wavefront.graph = graph;
+
+    // every event removes a vertex or a reflex vertex, this is just a safeguard for degenerate input
+    let max_events = 4 * wavefront.edges.len().pow(2);
+    let mut events = 0;
+    while let Some((t, event)) = wavefront.next_event() {
+        if events == max_events {
+            return Err(GeometryError::NotConverged);
+        }
+        events += 1;
+        wavefront.advance(t);
+        match event {
+            Event::Edge { ring, index } => wavefront.edge_event(ring, index),
+            Event::Split {
+                ring,
+                index,
+                edge_ring,
+                edge_index,
+            } => wavefront.split_event(ring, index, edge_ring, edge_index),
+        }
+        wavefront.close_collapsed_loops();
+    }
+
+    let mut graph = wavefront.graph;
+    for face in 0..graph.faces.len() {
+        trace_face(&mut graph, face);
+    }
+    Ok(graph)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WavefrontEdge {
+    dir: DVec2,
+    /// Points to the inside of the polygon
+    normal: DVec2,
+    weight: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Vertex {
+    position: DVec2,
+    velocity: DVec2,
+    /// The node where the vertex started
+    node: usize,
+    /// The edge ending at this vertex
+    left: usize,
+    /// The edge starting at this vertex
+    right: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Event {
+    /// The edge starting at the vertex `index` shrinks to a point
+    Edge { ring: usize, index: usize },
+    /// The reflex vertex `index` hits the edge starting at `edge_index`
+    Split {
+        ring: usize,
+        index: usize,
+        edge_ring: usize,
+        edge_index: usize,
+    },
+}
+
+#[derive(Debug, Default)]
+struct Wavefront {
+    edges: Vec<WavefrontEdge>,
+    loops: Vec<Vec<Vertex>>,
+    graph: SkeletonGraph<DVec2>,
+    time: f64,
+    eps: f64,
+}
+
+impl Wavefront {
+    /// The velocity of a vertex such that both of its edges move with their speed
+    fn velocity(&self, left: usize, right: usize) -> DVec2 {
+        let (a, b) = (self.edges[left], self.edges[right]);
+        let det = a.normal.perp_dot(b.normal);
+        if det.abs() <= 1e-12 {
+            if a.normal.dot(b.normal) > 0.0 {
+                a.normal * (a.weight + b.weight) * 0.5
+            } else {
+                // the edges are anti-parallel and the vertex is about to vanish
+                DVec2::ZERO
+            }
+        } else {
+            DVec2::new(
+                a.weight * b.normal.y - b.weight * a.normal.y,
+                a.normal.x * b.weight - b.normal.x * a.weight,
+            ) / det
+        }
+    }
+
+    fn is_reflex(&self, vertex: &Vertex) -> bool {
+        self.edges[vertex.left]
+            .dir
+            .perp_dot(self.edges[vertex.right].dir)
+            < -1e-12
+    }
+
+    /// The earliest event relative to the current time
+    fn next_event(&self) -> Option<(f64, Event)> {
+        let mut best: Option<(f64, Event)> = None;
+        let mut consider = |t: f64, event: Event| {
+            if best.is_none_or(|(best_t, _)| t < best_t) {
+                best = Some((t.max(0.0), event));
+            }
+        };
+
+        for (ring, vertices) in self.loops.iter().enumerate() {
+            let n = vertices.len();
+            for (index, a) in vertices.iter().enumerate() {
+                let b = vertices[(index + 1) % n];
+                let dir = self.edges[a.right].dir;
+                let length = (b.position - a.position).dot(dir);
+                let rate = (b.velocity - a.velocity).dot(dir);
+                if length <= self.eps {
+                    consider(0.0, Event::Edge { ring, index });
+                } else if rate < 0.0 {
+                    consider(-length / rate, Event::Edge { ring, index });
+                }
+            }
+        }
+
+        for (ring, vertices) in self.loops.iter().enumerate() {
+            for (index, r) in vertices.iter().enumerate() {
+                if !self.is_reflex(r) {
+                    continue;
+                }
+                for (edge_ring, others) in self.loops.iter().enumerate() {
+                    let n = others.len();
+                    for (edge_index, u) in others.iter().enumerate() {
+                        if u.right == r.left || u.right == r.right {
+                            continue;
+                        }
+                        let w = others[(edge_index + 1) % n];
+                        if let Some(t) = self.split_time(r, u, &w) {
+                            consider(
+                                t,
+                                Event::Split {
+                                    ring,
+                                    index,
+                                    edge_ring,
+                                    edge_index,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    /// When the vertex `r` hits the edge between `u` and `w`, if it does so at all
+    fn split_time(&self, r: &Vertex, u: &Vertex, w: &Vertex) -> Option<f64> {
+        let edge = self.edges[u.right];
+        let distance = edge.normal.dot(r.position - u.position);
+        let rate = edge.normal.dot(r.velocity) - edge.weight;
+        if distance < -self.eps || rate >= 0.0 {
+            return None;
+        }
+        let t = (distance / -rate).max(0.0);
+        let hit = r.position + r.velocity * t;
+        let (start, end) = (u.position + u.velocity * t, w.position + w.velocity * t);
+        let along = (hit - start).dot(edge.dir);
+        let length = (end - start).dot(edge.dir);
+        (along >= -self.eps && along <= length + self.eps).then_some(t)
+    }
+
+    fn advance(&mut self, t: f64) {
+        self.time += t;
+        self.loops
+            .iter_mut()
+            .flatten()
+            .for_each(|vertex| vertex.position += vertex.velocity * t);
+    }
+
+    /// Adds a node at the current time, or reuses one of the candidates if it's at the same place
+    fn node_at(&mut self, position: DVec2, candidates: &[usize]) -> usize {
+        candidates
+            .iter()
+            .copied()
+            .find(|candidate| {
+                let node = self.graph.nodes[*candidate];
+                node.position.distance(position) <= self.eps
+                    && (node.time - self.time).abs() <= self.eps
+            })
+            .unwrap_or_else(|| {
+                self.graph.nodes.push(SkeletonNode {
+                    position,
+                    time: self.time,
+                });
+                self.graph.nodes.len() - 1
+            })
+    }
+
+    /// Ends the path of a vertex at the given node
+    fn arc(&mut self, vertex: &Vertex, node: usize) {
+        if vertex.node != node {
+            self.graph.arcs.push(SkeletonArc {
+                nodes: [vertex.node, node],
+                faces: [vertex.left, vertex.right],
+            });
+        }
+    }
+
+    fn edge_event(&mut self, ring: usize, index: usize) {
+        let mut vertices = std::mem::take(&mut self.loops[ring]);
+        let next = (index + 1) % vertices.len();
+        let (a, b) = (vertices[index], vertices[next]);
+        let position = (a.position + b.position) * 0.5;
+        let node = self.node_at(position, &[a.node, b.node]);
+        self.arc(&a, node);
+        self.arc(&b, node);
+
+        vertices[index] = Vertex {
+            position,
+            velocity: self.velocity(a.left, b.right),
+            node,
+            left: a.left,
+            right: b.right,
+        };
+        vertices.remove(next);
+        self.loops[ring] = vertices;
+    }
+
+    fn split_event(&mut self, ring: usize, index: usize, edge_ring: usize, edge_index: usize) {
+        let r = self.loops[ring][index];
+        let edge = self.loops[edge_ring][edge_index].right;
+        let node = self.node_at(r.position, &[r.node]);
+        self.arc(&r, node);
+
+        let r1 = Vertex {
+            position: r.position,
+            velocity: self.velocity(r.left, edge),
+            node,
+            left: r.left,
+            right: edge,
+        };
+        let r2 = Vertex {
+            position: r.position,
+            velocity: self.velocity(edge, r.right),
+            node,
+            left: edge,
+            right: r.right,
+        };
+
+        // the vertices from `from` to `to` including both, wrapping around
+        let cyclic = |vertices: &[Vertex], from: usize, to: usize| {
+            let n = vertices.len();
+            let count = (to + n - from) % n + 1;
+            (0..count)
+                .map(|i| vertices[(from + i) % n])
+                .collect::<Vec<_>>()
+        };
+
+        let vertices = std::mem::take(&mut self.loops[ring]);
+        let n = vertices.len();
+        let (before, after) = ((index + n - 1) % n, (index + 1) % n);
+        if ring == edge_ring {
+            let first = [vec![r1], cyclic(&vertices, (edge_index + 1) % n, before)].concat();
+            let second = [vec![r2], cyclic(&vertices, after, edge_index)].concat();
+            self.loops[ring] = first;
+            self.loops.push(second);
+        } else {
+            let others = std::mem::take(&mut self.loops[edge_ring]);
+            let m = others.len();
+            self.loops[ring] = [
+                vec![r1],
+                cyclic(&others, (edge_index + 1) % m, edge_index),
+                vec![r2],
+                cyclic(&vertices, after, before),
+            ]
+            .concat();
+        }
+        self.loops.retain(|vertices| !vertices.is_empty());
+    }
+
+    /// Closes all loops which don't enclose any area anymore
+    fn close_collapsed_loops(&mut self) {
+        let (collapsed, loops) = std::mem::take(&mut self.loops)
+            .into_iter()
+            .partition::<Vec<_>, _>(|vertices| {
+                let n = vertices.len();
+                let (area, perimeter) = (0..n)
+                    .map(|i| (vertices[i].position, vertices[(i + 1) % n].position))
+                    .fold((0.0, 0.0), |(area, perimeter), (a, b)| {
+                        (area + a.perp_dot(b) * 0.5, perimeter + a.distance(b))
+                    });
+                n <= 2 || area.abs() <= self.eps * perimeter
+            });
+        self.loops = loops;
+        collapsed
+            .into_iter()
+            .for_each(|vertices| self.close_loop(&vertices));
+    }
+
+    /// Ends a loop without area. Such a loop runs back and forth along the final arcs, so every
+    /// arc is covered by two edges of the wavefront in opposite directions.
+    fn close_loop(&mut self, vertices: &[Vertex]) {
+        let mut nodes: Vec<usize> = vec![];
+        for vertex in vertices {
+            let candidates = [&nodes[..], &[vertex.node]].concat();
+            let node = self.node_at(vertex.position, &candidates);
+            self.arc(vertex, node);
+            nodes.push(node);
+        }
+
+        // split the edges wherever another vertex of the loop touches them
+        let n = vertices.len();
+        let mut halves: Vec<([usize; 2], usize)> = vec![];
+        for i in 0..n {
+            let (a, b) = (nodes[i], nodes[(i + 1) % n]);
+            if a == b {
+                continue;
+            }
+            let line = Line([a, b].map(|node| self.graph.nodes[node].position));
+            let mut touching = nodes
+                .iter()
+                .filter(|node| **node != a && **node != b)
+                .map(|node| (line.scalar_of(self.graph.nodes[*node].position), *node))
+                .filter(|(t, node)| {
+                    (0.0..=1.0).contains(t)
+                        && line
+                            .pos_scaled(*t)
+                            .distance(self.graph.nodes[*node].position)
+                            <= self.eps
+                })
+                .collect::<Vec<_>>();
+            touching.sort_by(|(t1, _), (t2, _)| t1.total_cmp(t2));
+            touching.dedup_by_key(|(_, node)| *node);
+            let chain = [
+                vec![a],
+                touching.into_iter().map(|(_, node)| node).collect(),
+                vec![b],
+            ]
+            .concat();
+            halves.extend(chain.windows(2).map(|w| ([w[0], w[1]], vertices[i].right)));
+        }
+
+        // the faces lie behind the wavefront edges, the one running along an arc is on its right
+        let mut used = vec![false; halves.len()];
+        for i in 0..halves.len() {
+            if used[i] {
+                continue;
+            }
+            used[i] = true;
+            let ([a, b], face) = halves[i];
+            let opposite = (0..halves.len())
+                .find(|j| !used[*j] && halves[*j].0 == [b, a])
+                .map(|j| {
+                    used[j] = true;
+                    halves[j].1
+                });
+            self.graph.arcs.push(SkeletonArc {
+                nodes: [a, b],
+                faces: [opposite.unwrap_or(face), face],
+            });
+        }
+    }
+}
+
+/// Walks along the arcs bounding a face. Arcs with the face on their left lead away from the
+/// input edge, arcs with the face on their right lead back to it.
+fn trace_face(graph: &mut SkeletonGraph<DVec2>, face: usize) {
+    let [start, end] = [graph.faces[face].nodes[0], graph.faces[face].nodes[1]];
+    let mut used = vec![false; graph.arcs.len()];
+    let mut nodes = vec![start, end];
+    let mut current = end;
+    while current != start {
+        let up = graph
+            .arcs
+            .iter()
+            .enumerate()
+            .find(|(i, arc)| !used[*i] && arc.nodes[0] == current && arc.faces[0] == face)
+            .map(|(i, arc)| (i, arc.nodes[1]));
+        let down = || {
+            graph
+                .arcs
+                .iter()
+                .enumerate()
+                .find(|(i, arc)| !used[*i] && arc.nodes[1] == current && arc.faces[1] == face)
+                .map(|(i, arc)| (i, arc.nodes[0]))
+        };
+        let Some((arc, next)) = up.or_else(down) else {
+            break;
+        };
+        used[arc] = true;
+        current = next;
+        if current != start {
+            nodes.push(current);
+        }
+    }
+    graph.faces[face].nodes = nodes;
+}
+
+#[cfg(test)]
+mod straight_skeleton_tests {
+    use super::*;
+    use crate::{Area, MultiRing};
+
+    fn skeleton(points: &[DVec2], holes: &[&[DVec2]]) -> SkeletonGraph<DVec2> {
+        let polygon = Polygon::new(
+            Ring::new(points.to_vec()),
+            MultiRing(holes.iter().map(|hole| Ring::new(hole.to_vec())).collect()),
+        );
+        weighted_straight_skeleton(&polygon, |_| 1.0).unwrap()
+    }
+
+    fn assert_faces_cover(graph: &SkeletonGraph<DVec2>, area: f64) {
+        let faces_area = (0..graph.faces.len())
+            .map(|face| graph.face_ring(face).area())
+            .sum::<f64>();
+        assert!((faces_area - area).abs() < 1e-9, "{faces_area} != {area}");
+    }
+
+    #[test]
+    fn rectangle() {
+        let graph = skeleton(
+            &[
+                DVec2::ZERO,
+                DVec2::new(4.0, 0.0),
+                DVec2::new(4.0, 2.0),
+                DVec2::new(0.0, 2.0),
+            ],
+            &[],
+        );
+
+        let ridge = graph.nodes[4..].to_vec();
+        assert_eq!(ridge.len(), 2);
+        assert!(ridge.iter().all(|node| (node.time - 1.0).abs() < 1e-9));
+        assert_eq!(graph.arcs.len(), 5);
+        // the long edges sweep trapezoids, the short ones triangles
+        assert_eq!(graph.faces[0].nodes.len(), 4);
+        assert_eq!(graph.faces[1].nodes.len(), 3);
+        assert_faces_cover(&graph, 8.0);
+    }
+
+    #[test]
+    fn reflex_vertex_splits_the_wavefront() {
+        // ┌─┐
+        // │ │
+        // │ └───┐
+        // └─────┘
+        let graph = skeleton(
+            &[
+                DVec2::ZERO,
+                DVec2::new(3.0, 0.0),
+                DVec2::new(3.0, 1.0),
+                DVec2::new(1.0, 1.0),
+                DVec2::new(1.0, 3.0),
+                DVec2::new(0.0, 3.0),
+            ],
+            &[],
+        );
+
+        // the reflex vertex travels diagonally towards the outer corner
+        let reflex_arc = graph.arcs.iter().find(|arc| arc.nodes[0] == 3).unwrap();
+        let end = graph.nodes[reflex_arc.nodes[1]];
+        assert!(end.position.abs_diff_eq(DVec2::splat(0.5), 1e-9));
+        assert!((end.time - 0.5).abs() < 1e-9);
+        assert!(graph.nodes.iter().all(|node| node.time <= 0.5 + 1e-9));
+        assert_faces_cover(&graph, 5.0);
+    }
+
+    #[test]
+    fn polygon_with_hole() {
+        let square = |size: f64, offset: f64| {
+            [DVec2::ZERO, DVec2::X, DVec2::ONE, DVec2::Y].map(|p| p * size + offset)
+        };
+        let graph = skeleton(&square(6.0, 0.0), &[&square(2.0, 2.0)]);
+
+        // the wavefronts of the exterior and the hole meet halfway
+        let max_time = graph.nodes.iter().map(|node| node.time).fold(0.0, f64::max);
+        assert!((max_time - 1.0).abs() < 1e-9);
+        assert_eq!(graph.faces.len(), 8);
+        assert_faces_cover(&graph, 32.0);
+    }
+
+    #[test]
+    fn reflex_vertices_meet() {
+        //     ┌─────┐
+        //     │     │
+        //     └─┐ ┌─┘
+        //       │ │
+        //       └─┘
+        let graph = skeleton(
+            &[
+                DVec2::ZERO,
+                DVec2::new(1.0, 0.0),
+                DVec2::new(1.0, -3.0),
+                DVec2::new(2.0, -3.0),
+                DVec2::new(2.0, 0.0),
+                DVec2::new(3.0, 0.0),
+                DVec2::new(3.0, 2.0),
+                DVec2::new(0.0, 2.0),
+            ],
+            &[],
+        );
+
+        // the neck of the tooth closes after half a unit and the body becomes a ridge
+        assert!(graph.nodes.iter().any(|node| {
+            node.position.abs_diff_eq(DVec2::new(1.5, 0.5), 1e-9) && (node.time - 0.5).abs() < 1e-9
+        }));
+        assert_eq!(
+            graph
+                .nodes
+                .iter()
+                .filter(|node| (node.time - 1.0).abs() < 1e-9)
+                .count(),
+            3
+        );
+        assert_faces_cover(&graph, 9.0);
+    }
+
+    #[test]
+    fn repeated_vertices_fail() {
+        // bypasses the deduplication of `Ring::new`, e.g. like points which collapse when mapped
+        let ring = Ring(vec![DVec2::ZERO, DVec2::X, DVec2::X, DVec2::ONE, DVec2::Y]);
+
+        let result = weighted_straight_skeleton(&ring.to_polygon(), |_| 1.0);
+
+        assert!(matches!(result, Err(GeometryError::InvalidGeometry)));
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+
+use geo::sweep::{Cross, Intersections, LineOrPoint};
+use num_traits::Float;
+
+use crate::{prelude::Line2DIntersection, Line, LinesIter, Point2};
+
+/// An intersection of two lines found by [`sweep_intersections`]
+#[derive(Clone, Copy, Debug)]
+pub struct SweepIntersection<P: Point2> {
+    /// The index of the geometry and the index of the line within the geometry for both lines,
+    /// ordered such that the first one is the smaller one
+    pub lines: [(usize, usize); 2],
+    /// The intersection of the first line with the second one, see [`Line::intersection`]
+    pub intersection: Line2DIntersection<P>,
+}
+
+/// A line together with where it came from, as input of the sweep
+#[derive(Debug, Clone)]
+struct SweepLine<P: Point2> {
+    geometry: usize,
+    index: usize,
+    line: Line<P>,
+    /// The line extended at both ends by the tolerance, this one is swept
+    reach: Line<P>,
+}
+
+impl<P: Point2> Cross for SweepLine<P> {
+    type Scalar = P::S;
+
+    fn line(&self) -> LineOrPoint<P::S> {
+        geo::Line::from(self.reach).into()
+    }
+}
+
+/// Extends a line at both ends by as much as [`Line::intersection`] accepts beyond its endpoints:
+/// `tolerance` times its length for crossing lines and `tolerance` for collinear ones
+fn extend<P: Point2>(line: Line<P>, tolerance: P::S) -> Line<P> {
+    let length = line.length();
+    if tolerance <= <P::S>::from(0.0) || length <= <P::S>::from(0.0) {
+        return line;
+    }
+    let extension = line.to_dst() * tolerance.max(tolerance / length);
+    Line([line.src() - extension, line.dst() + extension])
+}
+
+/// All intersections among the lines of a set of geometries, including the ones between lines of
+/// the same geometry. Uses the sweep line algorithm of Bentley and Ottmann, so it runs in
+/// O((n + k) log n) for n lines with k intersections instead of comparing all pairs of lines.
+///
+/// The pairs of lines are found by exact predicates on the lines extended by `tolerance` at both
+/// ends and then classified with [`Line::intersection`] and `tolerance`, so lines ending just
+/// short of another one are reported like [`Line::intersection`] does. Parallel lines which are
+/// closer than `tolerance` to each other without touching aren't found though, since the sweep
+/// only widens the lines along their direction, [`LineIntersectable`](crate::LineIntersectable)
+/// compares all pairs of lines and finds those as well. Lines with non-finite coordinates are
+/// skipped.
+/// The result is sorted by the lines.
+///
+/// The lines of a linestring or ring meet their neighbours at the shared points, use
+/// [`Line2DIntersection::is_true_intersection`] or
+/// [`SelfIntersections`](crate::SelfIntersections) to skip those.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// // a bow tie which crosses itself and a line touching one of its corners
+/// let bow_tie = Ring::new([Vec2::ZERO, Vec2::ONE, Vec2::X, Vec2::Y]);
+/// let whisker = LineString::new(vec![Vec2::ONE, Vec2::new(2.0, 2.0)]);
+///
+/// let intersections = sweep_intersections(&[bow_tie.to_linestring(), whisker], 0.0001);
+///
+/// let crossings = intersections
+///     .iter()
+///     .filter(|found| found.intersection.is_true_intersection())
+///     .collect::<Vec<_>>();
+/// assert_eq!(crossings.len(), 1);
+/// assert_eq!(crossings[0].lines, [(0, 0), (0, 2)]);
+/// assert_eq!(crossings[0].intersection.pos(), Some(Vec2::splat(0.5)));
+/// // the ring's corners and the touching whisker
+/// assert_eq!(intersections.len(), 1 + 4 + 2);
+/// ```
+pub fn sweep_intersections<P: Point2, T: LinesIter<P = P>>(
+    geometries: &[T],
+    tolerance: P::S,
+) -> Vec<SweepIntersection<P>> {
+    let lines = geometries
+        .iter()
+        .enumerate()
+        .flat_map(|(geometry, lines)| {
+            lines
+                .iter_lines()
+                .enumerate()
+                .map(move |(index, line)| SweepLine {
+                    geometry,
+                    index,
+                    line,
+                    reach: extend(line, tolerance),
+                })
+        })
+        .filter(|line| {
+            line.reach
+                .0
+                .iter()
+                .all(|p| p.x().is_finite() && p.y().is_finite())
+        });
+
+    let mut seen = HashSet::new();
+    let mut intersections = Intersections::from_iter(lines)
+        .filter_map(|(a, b, _)| {
+            let (a, b) = if (a.geometry, a.index) <= (b.geometry, b.index) {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            let lines = [(a.geometry, a.index), (b.geometry, b.index)];
+            if !seen.insert(lines) {
+                return None;
+            }
+            let intersection = a.line.intersection(&b.line, tolerance);
+            intersection.intersect().then_some(SweepIntersection {
+                lines,
+                intersection,
+            })
+        })
+        .collect::<Vec<_>>();
+    intersections.sort_by_key(|found| found.lines);
+    intersections
+}
+
+#[cfg(test)]
+mod sweep_tests {
+    use bevy_math::DVec2;
+
+    use super::*;
+    use crate::{LineIntersectable, LineString, MultiRing, Polygon, Ring, SelfIntersections};
+
+    #[test]
+    fn same_pairs_as_brute_force() {
+        // deterministic pseudo random segments in the unit square
+        let mut seed = 12345u64;
+        let mut random = move || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+        let lines = (0..200)
+            .map(|_| {
+                let a = DVec2::new(random(), random());
+                LineString::new(vec![a, a + DVec2::new(random(), random()) * 0.2 - 0.1])
+            })
+            .collect::<Vec<_>>();
+
+        let swept = sweep_intersections(&lines, 1e-9)
+            .into_iter()
+            .map(|found| found.lines)
+            .collect::<Vec<_>>();
+
+        let mut brute_force = vec![];
+        for i in 0..lines.len() {
+            for j in i + 1..lines.len() {
+                let (a, b) = (lines[i].lines().next(), lines[j].lines().next());
+                if a.unwrap().intersection(&b.unwrap(), 1e-9).intersect() {
+                    brute_force.push([(i, 0), (j, 0)]);
+                }
+            }
+        }
+        assert!(!brute_force.is_empty());
+        assert_eq!(swept, brute_force);
+    }
+
+    #[test]
+    fn touching_and_overlapping_rings() {
+        let square = |size: f64, offset: DVec2| {
+            Ring::new([DVec2::ZERO, DVec2::X, DVec2::ONE, DVec2::Y].map(|p| p * size + offset))
+        };
+        let with_hole = Polygon::new(
+            square(3.0, DVec2::ZERO),
+            MultiRing(vec![square(1.0, DVec2::ONE)]),
+        );
+        assert!(with_hole.is_simple(1e-9));
+
+        // the hole touches the exterior from the inside
+        let touching = Polygon::new(
+            square(3.0, DVec2::ZERO),
+            MultiRing(vec![square(1.0, DVec2::new(2.0, 1.0))]),
+        );
+        let intersections = touching.self_intersections(1e-9);
+        // the shared edge, and the edges of the hole ending on it
+        assert_eq!(intersections.len(), 3);
+        let overlaps = intersections
+            .iter()
+            .filter(|found| matches!(found.intersection, Line2DIntersection::CollinearOverlap(_)))
+            .count();
+        assert_eq!(overlaps, 1);
+
+        // a figure eight which touches itself in a single point
+        let eight = Ring::new([
+            DVec2::ZERO,
+            DVec2::X,
+            DVec2::ONE,
+            DVec2::new(2.0, 2.0),
+            DVec2::new(1.0, 2.0),
+            DVec2::ONE,
+            DVec2::Y,
+        ]);
+        let intersections = eight.self_intersections(1e-9);
+        assert!(!intersections.is_empty());
+        assert!(intersections
+            .iter()
+            .all(|found| found.intersection.pos() == Some(DVec2::ONE)));
+
+        // a spike going back on itself
+        let spike = LineString::new(vec![DVec2::ZERO, DVec2::X * 2.0, DVec2::X]);
+        assert!(!spike.is_simple(1e-9));
+    }
+
+    #[test]
+    fn near_misses_within_tolerance() {
+        let bar = LineString::new(vec![DVec2::ZERO, DVec2::X]);
+        // ends half the tolerance short of the bar
+        let stem = LineString::new(vec![DVec2::new(0.5, 0.0005), DVec2::new(0.5, 1.0)]);
+        // continues the bar after a gap of half the tolerance
+        let extension = LineString::new(vec![DVec2::new(1.0005, 0.0), DVec2::X * 2.0]);
+        let lines = [bar, stem, extension];
+
+        let found = sweep_intersections(&lines, 0.001);
+        assert_eq!(
+            found.iter().map(|found| found.lines).collect::<Vec<_>>(),
+            [[(0, 0), (1, 0)], [(0, 0), (2, 0)]]
+        );
+        assert!(matches!(
+            found[1].intersection,
+            Line2DIntersection::CollinearTouch(_)
+        ));
+        // without tolerance they are apart
+        assert!(sweep_intersections(&lines, 0.0).is_empty());
+
+        // parallel lines within the tolerance without touching are only found by comparing all
+        // pairs of lines
+        let above = LineString::new(vec![DVec2::new(0.0, 0.0005), DVec2::new(1.0, 0.0005)]);
+        assert_eq!(lines[0].line_intersections(&above, 0.001).count(), 1);
+        assert!(sweep_intersections(&[lines[0].clone(), above], 0.001).is_empty());
+    }
+}
@@ -0,0 +1,405 @@
+use std::collections::HashMap;
+
+use bevy_math::DVec2;
+use geo::TriangulateSpade as _;
+use spade::{
+    AngleLimit, ConstrainedDelaunayTriangulation, Point2, RefinementParameters, Triangulation as _,
+};
+
+use crate::{
+    errors::GeometryError, Polygon, RefinementConfig, TriangulationConfig, TriangulationMethod,
+};
+
+/// Triangles referencing their vertices by index. The first vertices are the ones of the input
+/// polygon, ring by ring in the order of [`Polygon::iter_rings`], followed by vertices which were
+/// added by the triangulation. All triangles are oriented counter-clockwise.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IndexedTriangles {
+    pub vertices: Vec<DVec2>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+pub(crate) fn triangulate_polygon(
+    polygon: &Polygon<DVec2>,
+    config: TriangulationConfig,
+) -> Result<IndexedTriangles, GeometryError> {
+    let vertices = polygon
+        .iter_rings()
+        .flat_map(|ring| ring.points_open().iter().copied())
+        .collect::<Vec<_>>();
+    if !vertices.iter().all(|p| p.is_finite()) {
+        return Err(GeometryError::InvalidGeometry);
+    }
+    let snap_radius = if config.snap_radius.is_finite() {
+        config.snap_radius.max(0.0)
+    } else {
+        0.0
+    };
+
+    let mut offset = 0;
+    let mut rings = vec![];
+    for ring in polygon.iter_rings() {
+        let len = ring.points_open().len();
+        rings.push(clean_ring(&vertices, offset..offset + len, snap_radius));
+        offset += len;
+    }
+    // without an exterior there is nothing to triangulate
+    if rings.first().is_none_or(|ring| ring.is_empty()) {
+        return Ok(IndexedTriangles {
+            vertices,
+            triangles: vec![],
+        });
+    }
+
+    match config.method {
+        TriangulationMethod::EarClipping => {
+            let triangles = ear_clipping(&vertices, rings)?;
+            Ok(IndexedTriangles {
+                vertices,
+                triangles,
+            })
+        }
+        TriangulationMethod::ConstrainedDelaunay => {
+            constrained_delaunay(vertices, &rings, snap_radius)
+        }
+        TriangulationMethod::Refined(refinement) => refined_delaunay(vertices, &rings, refinement),
+    }
+}
+
+fn orient(a: DVec2, b: DVec2, c: DVec2) -> f64 {
+    (b - a).perp_dot(c - a)
+}
+
+/// Merges consecutive vertices within the snap radius and orients the ring counter-clockwise.
+/// Rings without area end up empty.
+fn clean_ring(vertices: &[DVec2], range: std::ops::Range<usize>, snap_radius: f64) -> Vec<usize> {
+    let mut ring = range.collect::<Vec<_>>();
+    ring.dedup_by(|b, a| vertices[*a].distance(vertices[*b]) <= snap_radius);
+    while ring.len() > 1
+        && vertices[ring[0]].distance(vertices[ring[ring.len() - 1]]) <= snap_radius
+    {
+        ring.pop();
+    }
+    if ring.len() < 3 {
+        return vec![];
+    }
+
+    let n = ring.len();
+    let area = (0..n)
+        .map(|i| vertices[ring[i]].perp_dot(vertices[ring[(i + 1) % n]]))
+        .sum::<f64>();
+    let scale = ring
+        .iter()
+        .map(|i| vertices[*i].distance(vertices[ring[0]]))
+        .fold(0.0, f64::max);
+    if area.abs() <= scale * scale * 1e-12 {
+        return vec![];
+    }
+    if area < 0.0 {
+        ring.reverse();
+    }
+    ring
+}
+
+/// Ear clipping of the exterior after the holes were connected to it
+fn ear_clipping(
+    vertices: &[DVec2],
+    mut rings: Vec<Vec<usize>>,
+) -> Result<Vec<[usize; 3]>, GeometryError> {
+    let mut outer = rings.remove(0);
+    let mut holes = rings
+        .into_iter()
+        .filter(|hole| !hole.is_empty())
+        .map(|mut hole| {
+            hole.reverse();
+            hole
+        })
+        .collect::<Vec<_>>();
+    // connecting the holes from right to left ensures that the bridges don't cross each other
+    let max_x = |hole: &Vec<usize>| {
+        hole.iter()
+            .map(|i| vertices[*i].x)
+            .fold(f64::NEG_INFINITY, f64::max)
+    };
+    holes.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+    for hole in holes {
+        bridge_hole(vertices, &mut outer, hole)?;
+    }
+
+    let (min, max) = outer
+        .iter()
+        .fold((DVec2::INFINITY, DVec2::NEG_INFINITY), |(min, max), i| {
+            (min.min(vertices[*i]), max.max(vertices[*i]))
+        });
+    let eps = (max - min).length_squared() * 1e-14;
+
+    let mut triangles = vec![];
+    let mut polygon = outer;
+    let mut cursor = 0;
+    while polygon.len() > 3 {
+        let n = polygon.len();
+        let corner = |i: usize| [(i + n - 1) % n, i, (i + 1) % n].map(|j| vertices[polygon[j]]);
+        let is_ear = |i: usize| {
+            let [a, b, c] = corner(i);
+            orient(a, b, c) > eps
+                && polygon.iter().all(|j| {
+                    let p = vertices[*j];
+                    // vertices on the boundary of the ear also block it
+                    p == a
+                        || p == b
+                        || p == c
+                        || orient(a, b, p) < 0.0
+                        || orient(b, c, p) < 0.0
+                        || orient(c, a, p) < 0.0
+                })
+        };
+
+        let ear = (0..n).map(|k| (cursor + k) % n).find(|i| is_ear(*i));
+        if let Some(i) = ear {
+            triangles.push([(i + n - 1) % n, i, (i + 1) % n].map(|j| polygon[j]));
+            polygon.remove(i);
+            cursor = i.saturating_sub(1);
+            continue;
+        }
+        // vertices without area (e.g. collinear or spikes) can be dropped
+        let flat = (0..n).find(|i| {
+            let [a, b, c] = corner(*i);
+            orient(a, b, c).abs() <= eps
+        });
+        let Some(i) = flat else {
+            // only possible if the polygon intersects itself
+            return Err(GeometryError::InvalidGeometry);
+        };
+        polygon.remove(i);
+    }
+    if let [a, b, c] = polygon[..] {
+        if orient(vertices[a], vertices[b], vertices[c]) > eps {
+            triangles.push([a, b, c]);
+        }
+    }
+    Ok(triangles)
+}
+
+/// Connects a (clockwise) hole to the (counter-clockwise) outer ring by a pair of edges from the
+/// rightmost vertex of the hole to a visible vertex of the outer ring
+fn bridge_hole(
+    vertices: &[DVec2],
+    outer: &mut Vec<usize>,
+    hole: Vec<usize>,
+) -> Result<(), GeometryError> {
+    let start = (0..hole.len())
+        .max_by(|a, b| vertices[hole[*a]].x.total_cmp(&vertices[hole[*b]].x))
+        .ok_or(GeometryError::InvalidGeometry)?;
+    let m = vertices[hole[start]];
+    let n = outer.len();
+
+    // the closest edge hit by a ray from `m` towards +x
+    let mut hit: Option<(f64, usize)> = None;
+    for i in 0..n {
+        let (a, b) = (vertices[outer[i]], vertices[outer[(i + 1) % n]]);
+        if a.y > m.y || b.y < m.y || a.y == b.y {
+            continue;
+        }
+        let x = a.x + (m.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x >= m.x && hit.is_none_or(|(best, _)| x < best) {
+            let candidate = if a.x > b.x { i } else { (i + 1) % n };
+            hit = Some((x, candidate));
+        }
+    }
+    let (x, mut candidate) = hit.ok_or(GeometryError::InvalidGeometry)?;
+
+    // reflex vertices within the triangle of `m`, the hit and the candidate may block the view,
+    // then the one with the smallest angle to the ray is visible
+    let i_point = DVec2::new(x, m.y);
+    let p = vertices[outer[candidate]];
+    if p != i_point {
+        let (a, b, c) = if m.y < p.y {
+            (m, i_point, p)
+        } else {
+            (m, p, i_point)
+        };
+        let mut best = (f64::INFINITY, f64::INFINITY);
+        for i in 0..n {
+            let q = vertices[outer[i]];
+            let [prev, next] = [(i + n - 1) % n, (i + 1) % n].map(|j| vertices[outer[j]]);
+            let inside = orient(a, b, q) >= 0.0 && orient(b, c, q) >= 0.0 && orient(c, a, q) >= 0.0;
+            if q == p || !inside || orient(prev, q, next) >= 0.0 {
+                continue;
+            }
+            let angle = ((q.y - m.y).abs() / (q.x - m.x), q.x - m.x);
+            if angle < best {
+                best = angle;
+                candidate = i;
+            }
+        }
+    }
+
+    // the same position may occur several times after earlier bridges, take the one facing `m`
+    let q = vertices[outer[candidate]];
+    if let Some(i) = (0..n).find(|i| {
+        let [prev, next] = [(i + n - 1) % n, (i + 1) % n].map(|j| vertices[outer[j]]);
+        vertices[outer[*i]] == q
+            && if orient(prev, q, next) >= 0.0 {
+                orient(prev, q, m) >= 0.0 && orient(q, next, m) >= 0.0
+            } else {
+                orient(prev, q, m) >= 0.0 || orient(q, next, m) >= 0.0
+            }
+    }) {
+        candidate = i;
+    }
+
+    let spliced = outer[..=candidate]
+        .iter()
+        .chain(&hole[start..])
+        .chain(&hole[..=start])
+        .chain(&outer[candidate..])
+        .copied()
+        .collect();
+    *outer = spliced;
+    Ok(())
+}
+
+fn constrained_delaunay(
+    mut vertices: Vec<DVec2>,
+    rings: &[Vec<usize>],
+    snap_radius: f64,
+) -> Result<IndexedTriangles, GeometryError> {
+    let to_geo = |ring: &Vec<usize>| {
+        geo::LineString::from(
+            ring.iter()
+                .map(|i| geo::coord! { x: vertices[*i].x, y: vertices[*i].y })
+                .collect::<Vec<_>>(),
+        )
+    };
+    let polygon = geo::Polygon::new(
+        to_geo(&rings[0]),
+        rings[1..]
+            .iter()
+            .filter(|ring| !ring.is_empty())
+            .map(to_geo)
+            .collect(),
+    );
+    let triangles = polygon
+        .constrained_triangulation(geo::triangulate_spade::SpadeTriangulationConfig { snap_radius })
+        .map_err(|_| GeometryError::InvalidGeometry)?;
+
+    // the triangulation may move vertices within the snap radius or add intersection points
+    let used = rings.iter().flatten().copied().collect::<Vec<_>>();
+    let mut lookup = used
+        .iter()
+        .map(|i| ((vertices[*i].x.to_bits(), vertices[*i].y.to_bits()), *i))
+        .collect::<HashMap<_, _>>();
+    let mut index_of = |coord: geo::Coord<f64>| {
+        let p = DVec2::new(coord.x, coord.y);
+        *lookup
+            .entry((p.x.to_bits(), p.y.to_bits()))
+            .or_insert_with(|| {
+                used.iter()
+                    .copied()
+                    .filter(|i| vertices[*i].distance(p) <= snap_radius)
+                    .min_by(|a, b| {
+                        vertices[*a]
+                            .distance(p)
+                            .total_cmp(&vertices[*b].distance(p))
+                    })
+                    .unwrap_or_else(|| {
+                        vertices.push(p);
+                        vertices.len() - 1
+                    })
+            })
+    };
+    let triangles = triangles
+        .into_iter()
+        .map(|triangle| triangle.to_array().map(&mut index_of))
+        .collect::<Vec<_>>();
+    let triangles = triangles
+        .into_iter()
+        .filter_map(|[a, b, c]| {
+            let area = orient(vertices[a], vertices[b], vertices[c]);
+            if area > 0.0 {
+                Some([a, b, c])
+            } else if area < 0.0 {
+                Some([a, c, b])
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    Ok(IndexedTriangles {
+        vertices,
+        triangles,
+    })
+}
+
+/// Delaunay refinement after Ruppert and Chew, which inserts vertices until all triangles satisfy
+/// the bounds of the config
+fn refined_delaunay(
+    mut vertices: Vec<DVec2>,
+    rings: &[Vec<usize>],
+    config: RefinementConfig,
+) -> Result<IndexedTriangles, GeometryError> {
+    let mut cdt = ConstrainedDelaunayTriangulation::<Point2<f64>>::new();
+    let mut index_of = HashMap::new();
+    let mut handles = vec![];
+    for ring in rings.iter().filter(|ring| !ring.is_empty()) {
+        let ring_handles = ring
+            .iter()
+            .map(|i| {
+                let handle = cdt
+                    .insert(Point2::new(vertices[*i].x, vertices[*i].y))
+                    .map_err(|_| GeometryError::InvalidGeometry)?;
+                index_of.entry(handle.index()).or_insert(*i);
+                Ok(handle)
+            })
+            .collect::<Result<Vec<_>, GeometryError>>()?;
+        handles.push(ring_handles);
+    }
+    for ring in handles {
+        for (from, to) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+            // crossing constraints mean that the polygon intersects itself
+            if !cdt.can_add_constraint(*from, *to) {
+                return Err(GeometryError::InvalidGeometry);
+            }
+            cdt.add_constraint(*from, *to);
+        }
+    }
+
+    let mut parameters = RefinementParameters::new()
+        .exclude_outer_faces(true)
+        .with_angle_limit(AngleLimit::from_rad(config.min_angle.max(0.0)));
+    if config.max_area.is_finite() {
+        parameters = parameters.with_max_allowed_area(config.max_area);
+    }
+    if let Some(max) = config.max_additional_vertices {
+        parameters = parameters.with_max_additional_vertices(max);
+    }
+    let result = cdt.refine(parameters);
+    if !result.refinement_complete {
+        return Err(GeometryError::NotConverged);
+    }
+    let excluded = result
+        .excluded_faces
+        .into_iter()
+        .collect::<std::collections::HashSet<_>>();
+
+    for vertex in cdt.vertices() {
+        index_of.entry(vertex.fix().index()).or_insert_with(|| {
+            vertices.push(DVec2::new(vertex.position().x, vertex.position().y));
+            vertices.len() - 1
+        });
+    }
+    let triangles = cdt
+        .inner_faces()
+        .filter(|face| !excluded.contains(&face.fix()))
+        .map(|face| {
+            face.vertices()
+                .map(|vertex| index_of[&vertex.fix().index()])
+        })
+        .collect();
+
+    Ok(IndexedTriangles {
+        vertices,
+        triangles,
+    })
+}
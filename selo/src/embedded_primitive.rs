@@ -1,23 +1,29 @@
-use bevy_math::{Vec2, Vec3};
+use bevy_math::{DVec2, DVec3, Vec2, Vec3};
 
-use crate::{prelude::Workplane, Map};
+use crate::{
+    errors::GeometryError,
+    prelude::{DWorkplane, Workplane},
+    Area, BoolOps, BufferGeometry, ContainsGeometry, IndexedMesh, IntoOverlayResource, LocatePoint,
+    Map, MultiPolygon, Point2, PointLocation, Triangulate, TriangulationConfig,
+};
 
 /// A trait to classify 2D geometric types that can be created from points on a 3D workplane and
-/// the plane itself
-pub trait Unembed {
+/// the plane itself. The workplane is a [`Workplane`] for `f32` and a [`DWorkplane`] for `f64`
+/// coordinates.
+pub trait Unembed<W = Workplane> {
     /// Representation of the flat geometry in 3D coordinates
-    type Type3D: Embed<Type2D = Self>;
+    type Type3D: Embed<W, Type2D = Self>;
 
     /// method to transform the geometry from the XY plane with 2D coordinates back to a 3D plane
-    fn unembed(&self, workplane: Workplane) -> Self::Type3D;
+    fn unembed(&self, workplane: W) -> Self::Type3D;
 }
 
-pub trait Embed {
+pub trait Embed<W = Workplane> {
     /// Flattened representation of this 3D geometry
     type Type2D;
 
     /// method to transform the geometry from a 3D plane to the XY plane into 2D coordinates
-    fn embed(&self, workplane: Workplane) -> Self::Type2D;
+    fn embed(&self, workplane: W) -> Self::Type2D;
 }
 
 /// This type represents geometry in a 3D context which was projected to 2D coordinates to apply
@@ -31,13 +37,55 @@ pub trait Embed {
 ///
 /// let triangle_2d = FlatPrimitive::<Triangle<Vec2>>::new(Triangle([a,b,c]), plane);
 /// ```
+///
+/// The 2D traits like [`Area`], [`BufferGeometry`], [`Triangulate`] and [`ContainsGeometry`] and
+/// the operations of [`BoolOps`] are implemented directly on the flat primitive, so several
+/// operations can be applied in 2D with a single embedding and unembedding, which avoids the
+/// rounding drift of going back and forth. The boolean operations return flat primitives on the
+/// same workplane and can be chained, the results of the other operations are plain 2D geometry
+/// which can be put back onto the plane with [`FlatPrimitive::with_geometry`].
+///
+/// Other geometry taking part in the operations is plain 2D geometry, which has to be in the 2D
+/// coordinates of the same plane. Flat primitives aren't accepted there, so geometry on different
+/// workplanes can't be mixed by accident: [`Embed`] 3D geometry into the workplane first.
+///
+/// ```
+/// # use selo::prelude::*;
+/// // a wall with a door and a hatch
+/// let wall = Ring::new([Vec3::ZERO, Vec3::X * 4.0, Vec3::new(4.0, 0.0, 3.0), Vec3::Z * 3.0]);
+/// let door = Ring::new([
+///     Vec3::new(1.0, 0.0, 0.0),
+///     Vec3::new(2.0, 0.0, 0.0),
+///     Vec3::new(2.0, 0.0, 2.0),
+///     Vec3::new(1.0, 0.0, 2.0),
+/// ]);
+/// let plane = Workplane::from_primitive(&wall).unwrap();
+///
+/// let flat = FlatPrimitive::<Ring<Vec2>>::new(wall, plane);
+/// let door = door.embed(plane);
+/// let hatch = Ring::new([
+///     Vec3::new(3.0, 0.0, 0.0),
+///     Vec3::new(3.5, 0.0, 0.0),
+///     Vec3::new(3.5, 0.0, 1.0),
+///     Vec3::new(3.0, 0.0, 1.0),
+/// ]);
+/// let cut = flat.difference(&door).difference(&hatch.embed(plane));
+///
+/// assert!((cut.area() - 9.5).abs() < 0.001);
+/// assert!(flat.is_containing(&door) && !cut.is_containing(&door));
+/// let triangles = cut.triangulate(Default::default()).unwrap();
+/// assert!((triangles.0.iter().map(|t| t.area()).sum::<f32>() - 9.5).abs() < 0.001);
+///
+/// let (cut_3d, _) = cut.unpack();
+/// assert!(cut_3d.iter_points().all(|p| p.y.abs() < 1e-6));
+/// ```
 #[derive(Debug, Clone)]
-pub struct FlatPrimitive<P: Unembed> {
+pub struct FlatPrimitive<P: Unembed<W>, W = Workplane> {
     primitive: P,
-    workplane: Workplane,
+    workplane: W,
 }
 
-impl<A: Unembed> FlatPrimitive<A> {
+impl<A: Unembed<W>, W: Copy> FlatPrimitive<A, W> {
     /// Transforms a given 3D geometry that is flat with respect to some [`Workplane`] into 2D space
     ///
     /// ```
@@ -50,7 +98,7 @@ impl<A: Unembed> FlatPrimitive<A> {
     /// let triangle_2d = FlatPrimitive::<Triangle<Vec2>>::new(Triangle([a,b,c]), plane);
     /// ```
     #[inline]
-    pub fn new(from: A::Type3D, workplane: Workplane) -> Self {
+    pub fn new(from: A::Type3D, workplane: W) -> Self {
         Self {
             primitive: from.embed(workplane),
             workplane,
@@ -77,13 +125,63 @@ impl<A: Unembed> FlatPrimitive<A> {
     /// let flipped_triangle = triangle_2d.map_geometry(flip_triangle);
     /// ```
     #[inline]
-    pub fn map_geometry<B: Unembed>(self, f: impl Fn(A) -> B) -> FlatPrimitive<B> {
+    pub fn map_geometry<B: Unembed<W>>(self, f: impl Fn(A) -> B) -> FlatPrimitive<B, W> {
         FlatPrimitive {
             primitive: f(self.primitive),
             workplane: self.workplane,
         }
     }
 
+    /// Apply a fallible transformation to the flattened 2D geometry, see
+    /// [`FlatPrimitive::map_geometry`]
+    #[inline]
+    pub fn try_map_geometry<B: Unembed<W>>(
+        self,
+        f: impl FnOnce(A) -> Result<B, GeometryError>,
+    ) -> Result<FlatPrimitive<B, W>, GeometryError> {
+        Ok(FlatPrimitive {
+            primitive: f(self.primitive)?,
+            workplane: self.workplane,
+        })
+    }
+
+    /// Wraps geometry which already is in the 2D coordinates of the workplane
+    #[inline]
+    pub fn from_flat(primitive: A, workplane: W) -> Self {
+        Self {
+            primitive,
+            workplane,
+        }
+    }
+
+    /// Puts other 2D geometry onto the same workplane, e.g. the result of an operation on this
+    /// primitive
+    #[inline]
+    pub fn with_geometry<B: Unembed<W>>(&self, primitive: B) -> FlatPrimitive<B, W> {
+        FlatPrimitive {
+            primitive,
+            workplane: self.workplane,
+        }
+    }
+
+    /// The flattened 2D geometry
+    #[inline]
+    pub fn primitive(&self) -> &A {
+        &self.primitive
+    }
+
+    /// The workplane the 2D coordinates refer to
+    #[inline]
+    pub fn workplane(&self) -> W {
+        self.workplane
+    }
+
+    /// Transform a copy of the 2D geometry back into 3D space, see [`FlatPrimitive::unpack`]
+    #[inline]
+    pub fn to_3d(&self) -> A::Type3D {
+        self.primitive.unembed(self.workplane)
+    }
+
     /// Transform the 2D geometry back into 3D space onto the [`Workplane`] where it came from.
     ///
     /// ```
@@ -107,7 +205,7 @@ impl<A: Unembed> FlatPrimitive<A> {
     /// let (Triangle([a,b,c]), plane) = flipped_triangle.unpack();
     /// ```
     #[inline]
-    pub fn unpack(self) -> (A::Type3D, Workplane) {
+    pub fn unpack(self) -> (A::Type3D, W) {
         (A::unembed(&self.primitive, self.workplane), self.workplane)
     }
 }
@@ -134,3 +232,173 @@ impl<T: Map<Vec3, Vec2>> Embed for T {
         self.map(|p| proj.transform_point3(p).truncate())
     }
 }
+
+impl<T: Map<DVec2, DVec3>> Unembed<DWorkplane> for T
+where
+    T::Output: Embed<DWorkplane, Type2D = T>,
+{
+    type Type3D = T::Output;
+
+    #[inline]
+    fn unembed(&self, workplane: DWorkplane) -> Self::Type3D {
+        let inj = workplane.xy_injection();
+        self.map(|p| inj.transform_point3(p.extend(0.0)))
+    }
+}
+
+impl<T: Map<DVec3, DVec2>> Embed<DWorkplane> for T {
+    type Type2D = T::Output;
+
+    #[inline]
+    fn embed(&self, workplane: DWorkplane) -> Self::Type2D {
+        let proj = workplane.xy_projection();
+        self.map(|p| proj.transform_point3(p).truncate())
+    }
+}
+
+impl<A: Unembed<W> + Area, W> Area for FlatPrimitive<A, W> {
+    type P = A::P;
+
+    #[inline]
+    fn area(&self) -> <Self::P as crate::Wedge>::Output {
+        self.primitive.area()
+    }
+}
+
+impl<A: Unembed<W> + BufferGeometry, W> BufferGeometry for FlatPrimitive<A, W> {
+    type P = A::P;
+
+    #[inline]
+    fn buffer(&self, distance: f64) -> MultiPolygon<Self::P> {
+        self.primitive.buffer(distance)
+    }
+}
+
+/// The boolean operations of [`BoolOps`] on the flat geometry. The other geometry is plain 2D
+/// geometry in the coordinates of the same workplane, the result stays on the workplane so
+/// operations can be chained.
+impl<A, P, W: Copy> FlatPrimitive<A, W>
+where
+    A: Unembed<W> + BufferGeometry<P = P> + IntoOverlayResource<P = P>,
+    P: Point2,
+    MultiPolygon<P>: BufferGeometry<P = P> + Unembed<W>,
+{
+    /// See [`BoolOps::union`]
+    #[inline]
+    pub fn union<B>(&self, rhs: &B) -> FlatPrimitive<MultiPolygon<P>, W>
+    where
+        B: BufferGeometry<P = P> + IntoOverlayResource<P = P>,
+    {
+        self.with_geometry(self.primitive.union(rhs))
+    }
+
+    /// See [`BoolOps::union_approx`]
+    #[inline]
+    pub fn union_approx<B>(&self, rhs: &B, tolerance: f64) -> FlatPrimitive<MultiPolygon<P>, W>
+    where
+        B: BufferGeometry<P = P> + IntoOverlayResource<P = P>,
+    {
+        self.with_geometry(self.primitive.union_approx(rhs, tolerance))
+    }
+
+    /// See [`BoolOps::intersection`]
+    #[inline]
+    pub fn intersection<B>(&self, rhs: &B) -> FlatPrimitive<MultiPolygon<P>, W>
+    where
+        B: BufferGeometry<P = P> + IntoOverlayResource<P = P>,
+    {
+        self.with_geometry(self.primitive.intersection(rhs))
+    }
+
+    /// See [`BoolOps::intersection_approx`]
+    #[inline]
+    pub fn intersection_approx<B>(
+        &self,
+        rhs: &B,
+        tolerance: f64,
+    ) -> FlatPrimitive<MultiPolygon<P>, W>
+    where
+        B: BufferGeometry<P = P> + IntoOverlayResource<P = P>,
+    {
+        self.with_geometry(self.primitive.intersection_approx(rhs, tolerance))
+    }
+
+    /// See [`BoolOps::difference`]
+    #[inline]
+    pub fn difference<B>(&self, rhs: &B) -> FlatPrimitive<MultiPolygon<P>, W>
+    where
+        B: BufferGeometry<P = P> + IntoOverlayResource<P = P>,
+    {
+        self.with_geometry(self.primitive.difference(rhs))
+    }
+
+    /// See [`BoolOps::difference_approx`]
+    #[inline]
+    pub fn difference_approx<B>(&self, rhs: &B, tolerance: f64) -> FlatPrimitive<MultiPolygon<P>, W>
+    where
+        B: BufferGeometry<P = P> + IntoOverlayResource<P = P>,
+    {
+        self.with_geometry(self.primitive.difference_approx(rhs, tolerance))
+    }
+}
+
+impl<A: Unembed<W> + Triangulate, W> Triangulate for FlatPrimitive<A, W> {
+    type P = A::P;
+
+    #[inline]
+    fn triangulate_indexed(
+        &self,
+        config: TriangulationConfig,
+    ) -> Result<IndexedMesh<Self::P>, GeometryError> {
+        self.primitive.triangulate_indexed(config)
+    }
+}
+
+impl<A: Unembed<W> + ContainsGeometry<O>, W, O> ContainsGeometry<O> for FlatPrimitive<A, W> {
+    type Rhs = A::Rhs;
+
+    #[inline]
+    fn is_containing(&self, rhs: &O) -> bool {
+        self.primitive.is_containing(rhs)
+    }
+}
+
+// 3D points are projected onto the workplane, the distance to it is the z coordinate then
+
+impl<A: Unembed + LocatePoint<P = Vec2>> LocatePoint for FlatPrimitive<A> {
+    type P = Vec3;
+
+    #[inline]
+    fn locate_point(&self, p: Vec3, tolerance: f32) -> PointLocation {
+        let projected = self.workplane.xy_projection().transform_point3(p);
+        if projected.z.abs() > tolerance {
+            return PointLocation::Outside;
+        }
+        self.primitive.locate_point(projected.truncate(), tolerance)
+    }
+
+    #[inline]
+    fn winding_number(&self, p: Vec3) -> Option<i32> {
+        let projected = self.workplane.xy_projection().transform_point3(p);
+        self.primitive.winding_number(projected.truncate())
+    }
+}
+
+impl<A: Unembed<DWorkplane> + LocatePoint<P = DVec2>> LocatePoint for FlatPrimitive<A, DWorkplane> {
+    type P = DVec3;
+
+    #[inline]
+    fn locate_point(&self, p: DVec3, tolerance: f64) -> PointLocation {
+        let projected = self.workplane.xy_projection().transform_point3(p);
+        if projected.z.abs() > tolerance {
+            return PointLocation::Outside;
+        }
+        self.primitive.locate_point(projected.truncate(), tolerance)
+    }
+
+    #[inline]
+    fn winding_number(&self, p: DVec3) -> Option<i32> {
+        let projected = self.workplane.xy_projection().transform_point3(p);
+        self.primitive.winding_number(projected.truncate())
+    }
+}
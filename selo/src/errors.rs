@@ -1,5 +1,23 @@
+use crate::{Line, Point};
+
 #[derive(Debug, derive_more::Display, derive_more::Error)]
 pub enum GeometryError {
     #[display("invalid geometry")]
     InvalidGeometry,
+    #[display("geometries are not coplanar")]
+    NotCoplanar,
+    /// An iterative algorithm stopped before reaching a valid result, e.g. due to degenerate input
+    #[display("algorithm did not converge")]
+    NotConverged,
+}
+
+/// Error of [`Stitch`](crate::Stitch)
+#[derive(Debug, Clone, PartialEq, derive_more::Display, derive_more::Error)]
+pub enum StitchError<P: Point> {
+    /// Edges which are shared by more than two triangles or by two triangles with the same
+    /// orientation
+    #[display("{} non-manifold edges", _0.len())]
+    NonManifoldEdges(#[error(not(source))] Vec<Line<P>>),
+    #[display("triangles are not coplanar")]
+    NotCoplanar,
 }
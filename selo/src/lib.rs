@@ -30,11 +30,11 @@ use bevy_math::*;
 pub mod prelude {
     pub use super::algorithms::*;
     pub use super::embedded_primitive::{Embed, FlatPrimitive, Unembed};
-    pub use super::errors::GeometryError;
+    pub use super::errors::{GeometryError, StitchError};
     pub use super::point::*;
     pub use super::primitives::*;
     pub use super::traits::*;
-    pub use super::workplane::Workplane;
+    pub use super::workplane::{DWorkplane, EmbeddingPlane, PlaneFit, Workplane, WorkplanePoint};
     pub use bevy_math::*;
 }
 
@@ -48,6 +48,8 @@ pub fn intersect_line_2d_point<P: Point2>(a: Line<P>, b: Line<P>) -> Option<P> {
     })
 }
 
+/// Triangulates the polygon with duplicated vertices, use
+/// [`Triangulate::triangulate_indexed`] to get a mesh which shares the vertices of the polygon.
 pub fn triangulate_glam<P: Point2>(polygon: Polygon<P>) -> Vec<Triangle<P>> {
     let triangles = geo::Polygon::<P::S>::from(&polygon)
         .constrained_triangulation(geo::triangulate_spade::SpadeTriangulationConfig {
@@ -61,6 +63,8 @@ pub fn triangulate_glam<P: Point2>(polygon: Polygon<P>) -> Vec<Triangle<P>> {
         .collect::<Vec<_>>()
 }
 
+/// Stitches the triangles with geo, returns an empty [`MultiPolygon`] if that fails. See
+/// [`Stitch`] for a variant which preserves holes and reports the offending edges.
 pub fn stitch_triangles_glam<P: Point2>(
     triangles: impl IntoIterator<Item = Triangle<P>>,
 ) -> MultiPolygon<P> {
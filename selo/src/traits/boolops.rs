@@ -1,19 +1,26 @@
 use std::iter::once;
 
-use bevy_math::{DVec2, Vec2};
+use num_traits::Float;
+
+use bevy_math::{DVec2, DVec3, Vec2, Vec3};
 use i_overlay::{
     core::{fill_rule::FillRule, overlay_rule::OverlayRule},
     float::{overlay::FloatOverlay, source::resource::OverlayResource},
     i_float::float::{number::FloatNumber, point::FloatPoint},
     i_shape::base::data::Contour,
 };
-use sealed_helper_traits::{IPoint2, IntoOverlayResource};
+pub(crate) use sealed_helper_traits::{IPoint2, IntoOverlayResource};
 
-use crate::{MultiPolygon, MultiRing, Point2, Polygon, Ring, Triangle};
+use crate::{
+    errors::GeometryError,
+    prelude::{EmbeddingPlane, WorkplanePoint},
+    Embed, IterPoints, MultiPolygon, MultiRing, Normal, Point, Point2, Point3, Polygon, Ring,
+    Triangle, Unembed,
+};
 
 use super::BufferGeometry;
 
-const FILL_RULE: FillRule = FillRule::EvenOdd;
+pub(crate) const FILL_RULE: FillRule = FillRule::EvenOdd;
 
 /// Boolean Operations trait for geometries. These are basic logical operations but for geometry.
 /// If a geometry is defined by `{ x | x in geometry }`, then these operations allow to combine two
@@ -222,6 +229,17 @@ fn boolops<Lhs: IntoOverlayResource, Rhs: IntoOverlayResource<P = Lhs::P>>(
     MultiPolygon(shapes.into_iter().flat_map(paths_to_poly).collect())
 }
 
+/// Unions all the areas of the given geometry, including overlaps of its parts with each other.
+///
+/// In contrast to [`boolops`], this uses the non-zero fill rule so all the parts need to have the
+/// same winding to not cancel each other out.
+pub(crate) fn union_non_zero<T: IntoOverlayResource>(geometry: &T) -> MultiPolygon<T::P> {
+    let shapes = FloatOverlay::with_subj(&geometry.to_overlay_resource())
+        .into_graph(FillRule::NonZero)
+        .extract_shapes(OverlayRule::Subject);
+    MultiPolygon(shapes.into_iter().flat_map(paths_to_poly).collect())
+}
+
 impl<
         Lhs: BufferGeometry<P = <Lhs as IntoOverlayResource>::P> + IntoOverlayResource,
         Rhs: BufferGeometry<P = <Lhs as IntoOverlayResource>::P>
@@ -233,6 +251,132 @@ where
 {
 }
 
+/// Boolean Operations for flat geometries in 3D space. See [`BoolOps`] for the semantics of the
+/// individual operations.
+///
+/// Both geometries are embedded into the [`Workplane`](crate::prelude::Workplane) of `self` (or of
+/// `rhs` if `self` is degenerate), the operation is applied in 2D and the result is transformed
+/// back onto the workplane. `DVec3` geometry uses a [`DWorkplane`](crate::prelude::DWorkplane) and
+/// stays in double precision throughout. If any point of the inputs is further away from that
+/// plane than `tolerance`, the operation fails with [`GeometryError::NotCoplanar`].
+///
+/// ```
+/// # use selo::prelude::*;
+/// let ring_points = [Vec3::ZERO, Vec3::X, Vec3::ONE, Vec3::Y + Vec3::Z];
+/// let ring1 = Ring::new(ring_points);
+/// let ring2 = Ring::new(ring_points.map(|pos3| pos3 + Vec3::X * 0.5));
+///
+/// let union = ring1.union(&ring2, 0.001).unwrap();
+///
+/// assert_eq!(union.len(), 1);
+/// assert!((union.area().length() - 1.5 * 2f32.sqrt()).abs() < 0.001);
+///
+/// let tilted = ring2.map(|pos3| pos3 + Vec3::Z * pos3.x);
+/// assert!(ring1.union(&tilted, 0.001).is_err());
+/// ```
+pub trait BoolOps3d<Rhs> {
+    type P: Point3;
+
+    /// Union boolean operation of two coplanar geometries. See [`BoolOps::union`].
+    fn union(
+        &self,
+        rhs: &Rhs,
+        tolerance: <Self::P as Point>::S,
+    ) -> Result<MultiPolygon<Self::P>, GeometryError>;
+
+    /// Intersection boolean operation of two coplanar geometries. See [`BoolOps::intersection`].
+    fn intersection(
+        &self,
+        rhs: &Rhs,
+        tolerance: <Self::P as Point>::S,
+    ) -> Result<MultiPolygon<Self::P>, GeometryError>;
+
+    /// Difference boolean operation of two coplanar geometries. See [`BoolOps::difference`].
+    fn difference(
+        &self,
+        rhs: &Rhs,
+        tolerance: <Self::P as Point>::S,
+    ) -> Result<MultiPolygon<Self::P>, GeometryError>;
+}
+
+fn coplanar_boolops<Lhs, Rhs, P>(
+    lhs: &Lhs,
+    rhs: &Rhs,
+    tolerance: P::S,
+    overlay_rule: OverlayRule,
+) -> Result<MultiPolygon<P>, GeometryError>
+where
+    P: WorkplanePoint,
+    Lhs: IterPoints<P = P> + Normal<P = P> + Embed<P::Workplane>,
+    Rhs: IterPoints<P = P> + Normal<P = P> + Embed<P::Workplane>,
+    Lhs::Type2D: IntoOverlayResource,
+    Rhs::Type2D: IntoOverlayResource<P = <Lhs::Type2D as IntoOverlayResource>::P>,
+    MultiPolygon<<Lhs::Type2D as IntoOverlayResource>::P>:
+        Unembed<P::Workplane, Type3D = MultiPolygon<P>>,
+{
+    let Ok(workplane) =
+        P::Workplane::from_primitive(lhs).or_else(|_| P::Workplane::from_primitive(rhs))
+    else {
+        return Ok(MultiPolygon::empty());
+    };
+    let (normal, distance) = workplane.normal_and_distance();
+    let coplanar = lhs
+        .iter_points()
+        .chain(rhs.iter_points())
+        .all(|p| (normal.dot(p) - distance).abs() <= tolerance);
+    if !coplanar {
+        return Err(GeometryError::NotCoplanar);
+    }
+    Ok(boolops(&lhs.embed(workplane), &rhs.embed(workplane), overlay_rule).unembed(workplane))
+}
+
+// Implemented for each pair of types separately since a blanket implementation for `Vec3` would
+// conflict with the one for `DVec3`
+macro_rules! impl_boolops_3d {
+    ($($lhs:ident),*) => {
+        $(
+            impl_boolops_3d!(@rhs $lhs; Triangle, Ring, MultiRing, Polygon, MultiPolygon);
+        )*
+    };
+    (@rhs $lhs:ident; $($rhs:ident),*) => {
+        $(
+            impl_boolops_3d!(@point $lhs; $rhs; Vec3);
+            impl_boolops_3d!(@point $lhs; $rhs; DVec3);
+        )*
+    };
+    (@point $lhs:ident; $rhs:ident; $point:ident) => {
+        impl BoolOps3d<$rhs<$point>> for $lhs<$point> {
+            type P = $point;
+
+            fn union(
+                &self,
+                rhs: &$rhs<$point>,
+                tolerance: <$point as Point>::S,
+            ) -> Result<MultiPolygon<$point>, GeometryError> {
+                coplanar_boolops(self, rhs, tolerance, OverlayRule::Union)
+            }
+
+            fn intersection(
+                &self,
+                rhs: &$rhs<$point>,
+                tolerance: <$point as Point>::S,
+            ) -> Result<MultiPolygon<$point>, GeometryError> {
+                coplanar_boolops(self, rhs, tolerance, OverlayRule::Intersect)
+            }
+
+            fn difference(
+                &self,
+                rhs: &$rhs<$point>,
+                tolerance: <$point as Point>::S,
+            ) -> Result<MultiPolygon<$point>, GeometryError> {
+                coplanar_boolops(self, rhs, tolerance, OverlayRule::Difference)
+            }
+        }
+    };
+}
+
+impl_boolops_3d!(Triangle, Ring, MultiRing, Polygon, MultiPolygon);
+
 // the helper traits should not be accessible by end-users of the library to prevent misuse and to
 // restrict the API size
 mod sealed_helper_traits {
@@ -329,7 +473,7 @@ mod sealed_helper_traits {
     }
 }
 
-fn paths_to_poly<P: IPoint2>(
+pub(crate) fn paths_to_poly<P: IPoint2>(
     paths: impl IntoIterator<Item = Contour<FloatPoint<P::S2>>>,
 ) -> Option<Polygon<P>> {
     let mut paths = paths.into_iter();
@@ -357,7 +501,9 @@ fn path_to_ring<P: IPoint2>(path: Contour<FloatPoint<P::S2>>) -> Ring<P> {
 #[cfg(test)]
 mod boolops_tests {
 
-    use crate::Area;
+    use bevy_math::Dir3;
+
+    use crate::{prelude::Workplane, Area, Flip, Map};
 
     use super::*;
 
@@ -470,4 +616,78 @@ mod boolops_tests {
         assert_eq!(intersection.len(), 1);
         assert_eq!(intersection.area(), 0.5);
     }
+
+    #[test]
+    fn verify_coplanar_3d_union() {
+        // same setup as `verify_union_winding_expectation`, but on a tilted plane
+        let wp = Workplane::from_normal_and_origin(Dir3::new(Vec3::ONE).unwrap(), Vec3::Z);
+        let ring_points = [Vec2::ZERO, Vec2::X * 0.5, Vec2::X * 0.5 + Vec2::Y, Vec2::Y];
+        let ring1 = Ring::new(ring_points).unembed(wp);
+        let ring2 = Ring::new(ring_points.map(|pos2| pos2 + Vec2::X * 0.5)).unembed(wp);
+
+        let union = ring1.union(&ring2, 0.001).unwrap();
+
+        assert_eq!(union.len(), 1);
+        assert!((union.area().length() - 1.0).abs() < 0.001);
+        assert!(union
+            .area()
+            .normalize()
+            .abs_diff_eq(Vec3::ONE.normalize(), 0.001));
+        assert!(union
+            .iter_points()
+            .all(|p| wp.project_point(p).abs_diff_eq(p, 0.001)));
+    }
+
+    #[test]
+    fn verify_coplanar_3d_opposite_windings() {
+        let ring_points = [Vec3::ZERO, Vec3::X, Vec3::X + Vec3::Y, Vec3::Y];
+        let ring1 = Ring::new(ring_points);
+        let ring2 = Ring::new(ring_points.map(|pos3| pos3 + Vec3::X * 0.5)).flip();
+
+        let difference = ring1.difference(&ring2, 0.001).unwrap();
+
+        assert_eq!(difference.len(), 1);
+        assert!(difference.area().abs_diff_eq(Vec3::Z * 0.5, 0.001));
+    }
+
+    #[test]
+    fn verify_not_coplanar_3d() {
+        let ring_points = [Vec3::ZERO, Vec3::X, Vec3::X + Vec3::Y, Vec3::Y];
+        let ring1 = Ring::new(ring_points).to_polygon();
+        let ring2 = Ring::new(ring_points.map(|pos3| pos3 + Vec3::Z * 0.1)).to_polygon();
+
+        assert!(matches!(
+            ring1.intersection(&ring2, 0.001),
+            Err(GeometryError::NotCoplanar)
+        ));
+        assert!(ring1.intersection(&ring2, 0.2).is_ok());
+    }
+
+    #[test]
+    fn coplanar_3d_far_from_the_origin() {
+        // millimetre details a few hundred kilometres away, beyond the precision of f32
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        let ring_points = [
+            DVec3::ZERO,
+            DVec3::X,
+            DVec3::new(1.0, 1.0, 1.0),
+            DVec3::Y + DVec3::Z,
+        ]
+        .map(|p| p * 0.01 + offset);
+        let ring1 = Ring::new(ring_points);
+        let ring2 = Ring::new(ring_points.map(|p| p + DVec3::X * 0.005));
+
+        let union = ring1.union(&ring2, 1e-9).unwrap();
+        let difference = ring1.difference(&ring2, 1e-9).unwrap();
+
+        let area = |mp: &MultiPolygon<DVec3>| mp.area().length();
+        assert!((area(&union) - 1.5e-4 * 2f64.sqrt()).abs() < 1e-9);
+        assert!((area(&difference) - 0.5e-4 * 2f64.sqrt()).abs() < 1e-9);
+        // a micrometre off the plane
+        let lifted = ring2.map(|p| p + DVec3::Z * 1e-6);
+        assert!(matches!(
+            ring1.union(&lifted, 1e-7),
+            Err(GeometryError::NotCoplanar)
+        ));
+    }
 }
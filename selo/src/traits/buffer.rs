@@ -1,7 +1,11 @@
-use crate::{prelude::Workplane, primitives::*, Embed, Map, Point, ToGeo, ToSelo, Unembed};
+use crate::{
+    prelude::{DWorkplane, Workplane},
+    primitives::*,
+    Embed, Map, Point, ToGeo, ToSelo, Unembed,
+};
 use bevy_math::{DVec2, DVec3, Vec2, Vec3};
 
-use super::Orient2d;
+use super::{BufferStyle, BufferWithStyle, Orient2d};
 
 /// Expand or shrink geometry in normal direction at every point
 ///
@@ -38,6 +42,8 @@ use super::Orient2d;
 /// assert_eq!(polygon.buffer(1.0)[0].exterior().clone(), expected)
 /// ```
 ///
+/// Lines and points are buffered with the default [`BufferStyle`] (round caps and joins), see
+/// [`BufferWithStyle`] for more control over the shape of the result.
 pub trait BufferGeometry {
     type P: Point;
 
@@ -80,9 +86,10 @@ impl BufferGeometry for Polygon<DVec3> {
     type P = DVec3;
 
     fn buffer(&self, distance: f64) -> MultiPolygon<<Self as BufferGeometry>::P> {
-        self.map(|p| p.as_vec3())
-            .buffer(distance)
-            .map(|p| p.as_dvec3())
+        DWorkplane::from_primitive(self)
+            .map_or(MultiPolygon::<<Self as BufferGeometry>::P>::empty(), |wp| {
+                self.embed(wp).buffer(distance).unembed(wp)
+            })
     }
 }
 
@@ -125,10 +132,12 @@ impl BufferGeometry for MultiPolygon<Vec3> {
 
 impl BufferGeometry for MultiPolygon<DVec3> {
     type P = DVec3;
+
     fn buffer(&self, distance: f64) -> MultiPolygon<<Self as BufferGeometry>::P> {
-        self.map(|p| p.as_vec3())
-            .buffer(distance)
-            .map(|p| p.as_dvec3())
+        DWorkplane::from_primitive(self)
+            .map_or(MultiPolygon::<<Self as BufferGeometry>::P>::empty(), |wp| {
+                self.embed(wp).buffer(distance).unembed(wp)
+            })
     }
 }
 
@@ -173,3 +182,30 @@ where
         )
     }
 }
+
+// lines and points don't have an area that could shrink, so they are only buffered via
+// `BufferWithStyle`
+macro_rules! impl_buffer_geom_via_style {
+    ($($typename:ty => $point:ty),*) => {
+        $(
+            impl BufferGeometry for $typename {
+                type P = $point;
+
+                fn buffer(&self, distance: f64) -> MultiPolygon<<Self as BufferGeometry>::P> {
+                    self.buffer_with_style(distance, BufferStyle::default())
+                }
+            }
+        )*
+    };
+}
+
+impl_buffer_geom_via_style!(
+    Vec2 => Vec2,
+    DVec2 => DVec2,
+    Line<Vec2> => Vec2,
+    Line<DVec2> => DVec2,
+    LineString<Vec2> => Vec2,
+    LineString<DVec2> => DVec2,
+    MultiLineString<Vec2> => Vec2,
+    MultiLineString<DVec2> => DVec2
+);
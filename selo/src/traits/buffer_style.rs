@@ -0,0 +1,547 @@
+use std::f64::consts::{PI, TAU};
+
+use bevy_math::{DVec2, DVec3, Vec2, Vec3};
+
+use crate::{
+    prelude::{DWorkplane, Workplane},
+    primitives::*,
+    BoolOps as _, Embed, Map, Orient2d as _, Point, Unembed,
+};
+
+use super::union_non_zero;
+
+/// Shape of the ends of open lines when buffering them with [`BufferWithStyle`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CapStyle {
+    /// Half circle around the end point
+    #[default]
+    Round,
+    /// The buffer ends exactly at the end point
+    Flat,
+    /// Half square around the end point, extending the line by the buffer distance
+    Square,
+}
+
+/// Shape of the outer corner where two consecutive lines meet when buffering them with
+/// [`BufferWithStyle`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum JoinStyle {
+    /// Circular arc around the corner
+    #[default]
+    Round,
+    /// Sharp corner where the offset lines meet. If the distance of the tip to the corner exceeds
+    /// `limit` times the buffer distance, the corner is beveled instead.
+    Miter { limit: f64 },
+    /// Straight line cutting off the corner
+    Bevel,
+}
+
+/// Configures the shape of the geometry created by [`BufferWithStyle`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BufferStyle {
+    pub cap: CapStyle,
+    pub join: JoinStyle,
+    /// Number of segments used to approximate a full circle. Round caps and joins use a fraction
+    /// of this depending on their angle.
+    pub arc_segments: usize,
+}
+
+impl Default for BufferStyle {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            cap: CapStyle::default(),
+            join: JoinStyle::default(),
+            arc_segments: 32,
+        }
+    }
+}
+
+/// Expand geometry by a distance with control over the shape of the corners and line ends.
+///
+/// In contrast to [`BufferGeometry`](crate::BufferGeometry), this is also implemented for lines
+/// and points, which get expanded into the area around them. A `distance <= 0.0` results in an
+/// empty [`MultiPolygon`] for them. Areas are expanded (`+`) or shrunk (`-`) just like with
+/// [`BufferGeometry`](crate::BufferGeometry).
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let centerline = LineString::new(vec![Vec2::ZERO, Vec2::X * 4.0, Vec2::new(4.0, 3.0)]);
+///
+/// let style = BufferStyle {
+///     cap: CapStyle::Square,
+///     join: JoinStyle::Miter { limit: 2.0 },
+///     ..Default::default()
+/// };
+/// let wall = centerline.buffer_with_style(0.5, style);
+///
+/// assert_eq!(wall.len(), 1);
+/// assert!((wall.area() - 8.0).abs() < 0.001);
+/// ```
+pub trait BufferWithStyle {
+    type P: Point;
+
+    fn buffer_with_style(
+        &self,
+        distance: f64,
+        style: BufferStyle,
+    ) -> MultiPolygon<<Self as BufferWithStyle>::P>;
+}
+
+impl BufferWithStyle for DVec2 {
+    type P = DVec2;
+
+    fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<DVec2> {
+        let mut stroke = Stroke::new(distance, style);
+        stroke.point(*self);
+        stroke.finish()
+    }
+}
+
+impl BufferWithStyle for Line<DVec2> {
+    type P = DVec2;
+
+    fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<DVec2> {
+        let mut stroke = Stroke::new(distance, style);
+        stroke.linestring(&self.0, false);
+        stroke.finish()
+    }
+}
+
+impl BufferWithStyle for LineString<DVec2> {
+    type P = DVec2;
+
+    fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<DVec2> {
+        let mut stroke = Stroke::new(distance, style);
+        stroke.linestring(&self.0, self.closed());
+        stroke.finish()
+    }
+}
+
+impl BufferWithStyle for MultiLineString<DVec2> {
+    type P = DVec2;
+
+    fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<DVec2> {
+        let mut stroke = Stroke::new(distance, style);
+        self.0
+            .iter()
+            .for_each(|linestring| stroke.linestring(&linestring.0, linestring.closed()));
+        stroke.finish()
+    }
+}
+
+impl BufferWithStyle for MultiPolygon<DVec2> {
+    type P = DVec2;
+
+    fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<DVec2> {
+        if distance == 0.0 {
+            return self.clone();
+        }
+        let oriented = self.orient_default();
+        let mut stroke = Stroke::new(distance.abs(), style);
+        oriented
+            .iter_rings()
+            .for_each(|ring| stroke.linestring(ring.points_open(), true));
+        if distance > 0.0 {
+            // the holes are oriented the other way around, so they cancel out the exterior with
+            // the non-zero fill rule unless they are covered by the stroke
+            stroke.pieces.extend(oriented.iter_rings().cloned());
+            stroke.finish()
+        } else {
+            oriented.difference(&stroke.finish())
+        }
+    }
+}
+
+impl BufferWithStyle for Polygon<DVec2> {
+    type P = DVec2;
+
+    fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<DVec2> {
+        self.to_multi().buffer_with_style(distance, style)
+    }
+}
+
+// f32 geometry is buffered in f64 precision, just like with `BufferGeometry`
+macro_rules! impl_buffer_with_style_f32 {
+    ($($typename:ty),*) => {
+        $(
+            impl BufferWithStyle for $typename {
+                type P = Vec2;
+
+                fn buffer_with_style(
+                    &self,
+                    distance: f64,
+                    style: BufferStyle,
+                ) -> MultiPolygon<Vec2> {
+                    self.map(|p: Vec2| p.as_dvec2())
+                        .buffer_with_style(distance, style)
+                        .map(|p| p.as_vec2())
+                }
+            }
+        )*
+    };
+}
+
+impl_buffer_with_style_f32!(
+    Vec2,
+    Line<Vec2>,
+    LineString<Vec2>,
+    MultiLineString<Vec2>,
+    Polygon<Vec2>,
+    MultiPolygon<Vec2>
+);
+
+// 3D geometry is buffered on its workplane, in the precision of its points
+macro_rules! impl_buffer_with_style_3d {
+    ($($typename:ident),*) => {
+        $(
+            impl_buffer_with_style_3d!(@point $typename; Vec3; Workplane);
+            impl_buffer_with_style_3d!(@point $typename; DVec3; DWorkplane);
+        )*
+    };
+    (@point $typename:ident; $point:ty; $workplane:ty) => {
+        impl BufferWithStyle for $typename<$point> {
+            type P = $point;
+
+            fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<$point> {
+                <$workplane>::from_primitive(self).map_or(MultiPolygon::empty(), |wp| {
+                    self.embed(wp)
+                        .buffer_with_style(distance, style)
+                        .unembed(wp)
+                })
+            }
+        }
+    };
+}
+
+impl_buffer_with_style_3d!(Polygon, MultiPolygon);
+
+impl<P> BufferWithStyle for Ring<P>
+where
+    P: Point,
+    Polygon<P>: BufferWithStyle<P = P>,
+{
+    type P = P;
+
+    fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<P> {
+        self.to_polygon().buffer_with_style(distance, style)
+    }
+}
+
+impl<P> BufferWithStyle for Triangle<P>
+where
+    P: Point,
+    Polygon<P>: BufferWithStyle<P = P>,
+{
+    type P = P;
+
+    fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<P> {
+        self.to_ring().buffer_with_style(distance, style)
+    }
+}
+
+impl<P> BufferWithStyle for MultiRing<P>
+where
+    P: Point,
+    MultiPolygon<P>: BufferWithStyle<P = P>,
+{
+    type P = P;
+
+    fn buffer_with_style(&self, distance: f64, style: BufferStyle) -> MultiPolygon<P> {
+        self.to_multipolygon().buffer_with_style(distance, style)
+    }
+}
+
+/// Collects the pieces that make up the area around lines and points. The pieces are all wound
+/// counter-clockwise and overlap each other, [`Stroke::finish`] merges them into the final area.
+struct Stroke {
+    distance: f64,
+    style: BufferStyle,
+    pieces: Vec<Ring<DVec2>>,
+}
+
+impl Stroke {
+    fn new(distance: f64, style: BufferStyle) -> Self {
+        Self {
+            distance,
+            style,
+            pieces: vec![],
+        }
+    }
+
+    fn finish(self) -> MultiPolygon<DVec2> {
+        if self.pieces.is_empty() {
+            return MultiPolygon::empty();
+        }
+        union_non_zero(&MultiRing(self.pieces))
+    }
+
+    fn push(&mut self, mut points: Vec<DVec2>) {
+        if self.distance <= 0.0 {
+            return;
+        }
+        let area = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| a.perp_dot(*b))
+            .sum::<f64>();
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+        if area < 0.0 {
+            points.reverse();
+        }
+        self.pieces.push(Ring::new(points));
+    }
+
+    /// Points on the arc with radius `distance` around `center`, starting at `center + from *
+    /// distance` and rotating counter-clockwise by `angle`
+    fn arc(&self, center: DVec2, from: DVec2, angle: f64) -> impl Iterator<Item = DVec2> {
+        let steps = ((angle.abs() / TAU) * self.style.arc_segments as f64)
+            .ceil()
+            .max(1.0) as usize;
+        let distance = self.distance;
+        (0..=steps).map(move |step| {
+            let rotation = DVec2::from_angle(angle * step as f64 / steps as f64);
+            center + rotation.rotate(from) * distance
+        })
+    }
+
+    fn point(&mut self, p: DVec2) {
+        let d = self.distance;
+        match self.style.cap {
+            CapStyle::Round => {
+                let mut circle = self.arc(p, DVec2::X, TAU).collect::<Vec<_>>();
+                circle.pop();
+                self.push(circle);
+            }
+            CapStyle::Square => {
+                self.push(vec![
+                    p + DVec2::new(-d, -d),
+                    p + DVec2::new(d, -d),
+                    p + DVec2::new(d, d),
+                    p + DVec2::new(-d, d),
+                ]);
+            }
+            CapStyle::Flat => {}
+        }
+    }
+
+    fn linestring(&mut self, points: &[DVec2], closed: bool) {
+        let mut points = points.to_vec();
+        points.dedup();
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        let closed = closed && points.len() > 2;
+        let [first, .., last] = points.as_slice() else {
+            if let Some(p) = points.first() {
+                self.point(*p);
+            }
+            return;
+        };
+        let (first, last) = (*first, *last);
+
+        let mut segments = points.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>();
+        if closed {
+            segments.push((last, first));
+        }
+
+        let d = self.distance;
+        for &(a, b) in segments.iter() {
+            let n = (b - a).normalize().perp() * d;
+            self.push(vec![a - n, b - n, b + n, a + n]);
+        }
+
+        let dirs = segments
+            .iter()
+            .map(|(a, b)| (*b - *a).normalize())
+            .collect::<Vec<_>>();
+        for (i, window) in dirs.windows(2).enumerate() {
+            self.join(segments[i].1, window[0], window[1]);
+        }
+        if closed {
+            self.join(first, dirs[dirs.len() - 1], dirs[0]);
+        } else {
+            self.cap(first, -dirs[0], self.style.cap);
+            self.cap(last, dirs[dirs.len() - 1], self.style.cap);
+        }
+    }
+
+    /// Adds the cap at point `p` where `dir` is the direction pointing away from the line
+    fn cap(&mut self, p: DVec2, dir: DVec2, cap: CapStyle) {
+        let d = self.distance;
+        let n = dir.perp();
+        match cap {
+            CapStyle::Round => {
+                let half_circle = self.arc(p, n, -PI).collect::<Vec<_>>();
+                self.push(half_circle);
+            }
+            CapStyle::Square => {
+                self.push(vec![
+                    p + n * d,
+                    p - n * d,
+                    p + (dir - n) * d,
+                    p + (dir + n) * d,
+                ]);
+            }
+            CapStyle::Flat => {}
+        }
+    }
+
+    /// Adds the join at vertex `v` between the incoming direction `a` and outgoing direction `b`
+    fn join(&mut self, v: DVec2, a: DVec2, b: DVec2) {
+        const EPS: f64 = 1e-9;
+        let d = self.distance;
+        let (cross, dot) = (a.perp_dot(b), a.dot(b));
+        if cross.abs() <= EPS {
+            if dot < 0.0 {
+                // the line turns back onto itself, so the join is basically a cap
+                let cap = match self.style.join {
+                    JoinStyle::Round => CapStyle::Round,
+                    JoinStyle::Miter { .. } => CapStyle::Square,
+                    JoinStyle::Bevel => CapStyle::Flat,
+                };
+                self.cap(v, a, cap);
+            }
+            return;
+        }
+
+        // the join is only needed on the outer side of the turn
+        let side = -cross.signum();
+        let (na, nb) = (a.perp() * side, b.perp() * side);
+        let bevel = vec![v, v + na * d, v + nb * d];
+        match self.style.join {
+            JoinStyle::Round => {
+                let fan = std::iter::once(v)
+                    .chain(self.arc(v, na, cross.atan2(dot)))
+                    .collect::<Vec<_>>();
+                self.push(fan);
+            }
+            JoinStyle::Miter { limit } => {
+                let half = na + nb;
+                // distance of the tip relative to the buffer distance
+                let ratio = 2.0 / half.length();
+                if ratio <= limit {
+                    self.push(vec![
+                        v,
+                        v + na * d,
+                        v + half.normalize() * d * ratio,
+                        v + nb * d,
+                    ]);
+                } else {
+                    self.push(bevel);
+                }
+            }
+            JoinStyle::Bevel => self.push(bevel),
+        }
+    }
+}
+
+#[cfg(test)]
+mod buffer_style_tests {
+    use super::*;
+    use crate::Area;
+
+    fn style(cap: CapStyle, join: JoinStyle) -> BufferStyle {
+        BufferStyle {
+            cap,
+            join,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn line_caps() {
+        let line = Line([DVec2::ZERO, DVec2::X * 2.0]);
+
+        let flat = line.buffer_with_style(0.5, style(CapStyle::Flat, JoinStyle::Round));
+        assert!((flat.area() - 2.0).abs() < 1e-9);
+
+        let square = line.buffer_with_style(0.5, style(CapStyle::Square, JoinStyle::Round));
+        assert!((square.area() - 3.0).abs() < 1e-9);
+
+        let round = line.buffer_with_style(0.5, style(CapStyle::Round, JoinStyle::Round));
+        assert!((round.area() - (2.0 + PI * 0.25)).abs() < 0.01);
+    }
+
+    #[test]
+    fn linestring_joins() {
+        //     │
+        //     │
+        //  ───┘
+        let linestring = LineString::new(vec![DVec2::ZERO, DVec2::X * 2.0, DVec2::new(2.0, 2.0)]);
+        let flat_area = 2.0 * 2.0 - 0.25;
+
+        let miter = linestring
+            .buffer_with_style(0.5, style(CapStyle::Flat, JoinStyle::Miter { limit: 2.0 }));
+        assert_eq!(miter.len(), 1);
+        assert!((miter.area() - (flat_area + 0.25)).abs() < 1e-9);
+
+        // a right angle has a miter ratio of sqrt(2)
+        let miter_limited = linestring
+            .buffer_with_style(0.5, style(CapStyle::Flat, JoinStyle::Miter { limit: 1.2 }));
+        let bevel = linestring.buffer_with_style(0.5, style(CapStyle::Flat, JoinStyle::Bevel));
+        assert!((bevel.area() - (flat_area + 0.125)).abs() < 1e-9);
+        assert!((miter_limited.area() - bevel.area()).abs() < 1e-9);
+
+        let round = linestring.buffer_with_style(0.5, style(CapStyle::Flat, JoinStyle::Round));
+        assert!((round.area() - (flat_area + PI * 0.0625)).abs() < 0.01);
+    }
+
+    #[test]
+    fn point_and_degenerate_lines() {
+        let round = DVec2::ONE.buffer_with_style(1.0, BufferStyle::default());
+        assert!((round.area() - PI).abs() < 0.03);
+
+        let square = LineString::new(vec![DVec2::ONE, DVec2::ONE])
+            .buffer_with_style(1.0, style(CapStyle::Square, JoinStyle::Round));
+        assert!((square.area() - 4.0).abs() < 1e-9);
+
+        let negative =
+            Line([DVec2::ZERO, DVec2::X]).buffer_with_style(-1.0, BufferStyle::default());
+        assert!(negative.is_empty());
+    }
+
+    #[test]
+    fn polygon_with_miter_joins() {
+        let square = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]);
+        let miter = style(CapStyle::Flat, JoinStyle::Miter { limit: 2.0 });
+
+        let expanded = square.buffer_with_style(1.0, miter);
+        assert_eq!(expanded.len(), 1);
+        assert!((expanded.area() - 9.0).abs() < 1e-5);
+
+        let shrunk = square.buffer_with_style(-0.25, miter);
+        assert_eq!(shrunk.len(), 1);
+        assert!((shrunk.area() - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn polygon_far_from_the_origin() {
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        // a vertical square, single precision only has steps of half a unit this far out
+        let square = Ring::new(
+            [DVec3::ZERO, DVec3::Y, DVec3::new(0.0, 1.0, 1.0), DVec3::Z].map(|p| p + offset),
+        );
+        let miter = style(CapStyle::Flat, JoinStyle::Miter { limit: 2.0 });
+
+        let expanded = square.buffer_with_style(0.25, miter);
+
+        assert_eq!(expanded.len(), 1);
+        assert!((expanded.area().length() - 2.25).abs() < 1e-6);
+        for p in expanded
+            .iter()
+            .flat_map(|polygon| polygon.exterior().points_open())
+        {
+            assert!((p.x - offset.x).abs() < 1e-6);
+            let local = *p - offset;
+            assert!(
+                (local.y.abs() - 0.25).abs() < 1e-6 || (local.y - 1.25).abs() < 1e-6,
+                "{local}"
+            );
+        }
+    }
+}
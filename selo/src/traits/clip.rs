@@ -0,0 +1,166 @@
+use i_overlay::{float::clip::FloatClip as _, string::clip::ClipRule};
+
+use crate::{LineString, LinesIter, MultiLineString};
+
+use super::{IPoint2, IntoOverlayResource, FILL_RULE};
+
+/// Determines where the parts of the lines which run exactly along the boundary of the area end
+/// up when clipping lines with [`ClipLines`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClipBoundary {
+    /// Parts on the boundary are considered to be inside of the area
+    Inside,
+    /// Parts on the boundary are considered to be outside of the area
+    Outside,
+    /// Parts on the boundary are dropped from the result
+    #[default]
+    Exclude,
+}
+
+/// Clips the lines of a geometry against an area. This is the line-vs-area counterpart of
+/// [`BoolOps`](crate::BoolOps).
+///
+/// The lines of `self` are cut at every intersection with the boundary of the area and each
+/// resulting piece is either inside or outside of the area. Consecutive pieces are merged into
+/// [`LineString`]s again, but the direction of the resulting [`LineString`]s is not guaranteed to
+/// match the input.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let square = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]);
+/// let line = Line([Vec2::new(-1.0, 0.5), Vec2::new(2.0, 0.5)]);
+///
+/// let inside = line.clip_inside(&square, ClipBoundary::Exclude);
+/// let outside = line.clip_outside(&square, ClipBoundary::Exclude);
+///
+/// assert_eq!(inside.0.len(), 1);
+/// assert_eq!(outside.0.len(), 2);
+/// ```
+pub trait ClipLines<Area> {
+    type P: IPoint2;
+
+    /// The parts of the lines which are inside of the area
+    fn clip_inside(&self, area: &Area, boundary: ClipBoundary) -> MultiLineString<Self::P>;
+
+    /// The parts of the lines which are outside of the area
+    fn clip_outside(&self, area: &Area, boundary: ClipBoundary) -> MultiLineString<Self::P>;
+
+    /// The parts of the lines which are inside and outside of the area, in this order
+    fn clip(
+        &self,
+        area: &Area,
+        boundary: ClipBoundary,
+    ) -> (MultiLineString<Self::P>, MultiLineString<Self::P>) {
+        (
+            self.clip_inside(area, boundary),
+            self.clip_outside(area, boundary),
+        )
+    }
+}
+
+impl<Lines, Area> ClipLines<Area> for Lines
+where
+    Lines: LinesIter,
+    Lines::P: IPoint2,
+    Area: IntoOverlayResource<P = Lines::P>,
+{
+    type P = Lines::P;
+
+    fn clip_inside(&self, area: &Area, boundary: ClipBoundary) -> MultiLineString<Self::P> {
+        clip(
+            self,
+            area,
+            ClipRule {
+                invert: false,
+                boundary_included: boundary == ClipBoundary::Inside,
+            },
+        )
+    }
+
+    fn clip_outside(&self, area: &Area, boundary: ClipBoundary) -> MultiLineString<Self::P> {
+        clip(
+            self,
+            area,
+            // i-overlay flips the meaning of `boundary_included` for inverted clips, it then
+            // toggles whether the boundary is considered to be inside or outside
+            ClipRule {
+                invert: true,
+                boundary_included: boundary != ClipBoundary::Outside,
+            },
+        )
+    }
+}
+
+fn clip<Lines, Area>(lines: &Lines, area: &Area, clip_rule: ClipRule) -> MultiLineString<Lines::P>
+where
+    Lines: LinesIter,
+    Lines::P: IPoint2,
+    Area: IntoOverlayResource<P = Lines::P>,
+{
+    let paths = lines
+        .iter_lines()
+        .map(|line| vec![line.src().to_ipoint(), line.dst().to_ipoint()])
+        .collect::<Vec<_>>();
+    let clipped = paths.clip_by(&area.to_overlay_resource(), FILL_RULE, clip_rule);
+    MultiLineString(
+        clipped
+            .into_iter()
+            .map(|path| LineString::new(path.into_iter().map(Lines::P::from_ipoint).collect()))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod clip_tests {
+    use bevy_math::*;
+
+    use super::*;
+    use crate::primitives::*;
+
+    fn length(mls: &MultiLineString<Vec2>) -> f32 {
+        mls.iter_lines().map(|line| line.length()).sum()
+    }
+
+    #[test]
+    fn linestring_through_polygon_with_hole() {
+        //   ┌───────────┐
+        //   │  ┌─────┐  │
+        // ──┼──┼─────┼──┼──
+        //   │  └─────┘  │
+        //   └───────────┘
+        let exterior = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p * 3.0));
+        let hole = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p + Vec2::ONE));
+        let polygon = Polygon::new(exterior, hole.to_multi());
+        let linestring = LineString::new(vec![
+            Vec2::new(-1.0, 1.5),
+            Vec2::new(1.5, 1.5),
+            Vec2::new(4.0, 1.5),
+        ]);
+
+        let (inside, outside) = linestring.clip(&polygon, ClipBoundary::Exclude);
+
+        assert_eq!(inside.0.len(), 2);
+        assert!((length(&inside) - 2.0).abs() < 0.001);
+        assert_eq!(outside.0.len(), 3);
+        assert!((length(&outside) - 3.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn boundary_handling() {
+        let square = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]).to_polygon();
+        // runs along the bottom edge of the square and then leaves it
+        let line = MultiLineString(vec![LineString::new(vec![Vec2::ZERO, Vec2::X * 2.0])]);
+
+        let inside = line.clip_inside(&square, ClipBoundary::Inside);
+        assert!((length(&inside) - 1.0).abs() < 0.001);
+        let inside = line.clip_inside(&square, ClipBoundary::Outside);
+        assert!(length(&inside) < 0.001);
+
+        let outside = line.clip_outside(&square, ClipBoundary::Outside);
+        assert!((length(&outside) - 2.0).abs() < 0.001);
+        let outside = line.clip_outside(&square, ClipBoundary::Exclude);
+        assert!((length(&outside) - 1.0).abs() < 0.001);
+    }
+}
@@ -22,7 +22,10 @@ use geo::Contains;
 ///     Vec2::new(2.0, 2.0),
 ///     Vec2::new(-2.0, 2.0),
 /// ]);
-/// assert!(outer.is_containing(&inner))
+/// assert!(outer.is_containing(&inner));
+/// // points are tested exactly, the boundary doesn't count as contained
+/// assert!(outer.is_containing(&Vec2::new(1.999_999_9, 0.0)));
+/// assert!(!outer.is_containing(&Vec2::new(2.0, 0.0)));
 /// ```
 pub trait ContainsGeometry<Other> {
     type Rhs;
@@ -285,4 +288,31 @@ mod contains_tests {
             assert!(outer.is_containing(&inner));
         });
     }
+
+    #[test]
+    fn points_close_to_the_boundary() {
+        let list = list_of_outer_geometry_for!(Vec2);
+        // the diagonal of the triangle is the hardest case for naive predicates
+        let on_diagonal = Vec2::new(300.0, 700.0);
+        let inside = Vec2::new(300.0, f32::from_bits(700f32.to_bits() - 1));
+        let outside = Vec2::new(300.0, f32::from_bits(700f32.to_bits() + 1));
+        assert!(!list[0].is_containing(&on_diagonal));
+        assert!(list[0].is_containing(&inside));
+        assert!(!list[0].is_containing(&outside));
+        list.iter().for_each(|outer| {
+            assert!(outer.is_containing(&Vec2::splat(0.001)));
+            assert!(!outer.is_containing(&(Vec2::X * 500.0)));
+            assert!(!outer.is_containing(&Vec2::splat(-0.001)));
+        });
+
+        let with_hole = Polygon::new(
+            Ring::new([Vec2::ZERO, Vec2::X * 3.0, Vec2::ONE * 3.0, Vec2::Y * 3.0]),
+            MultiRing(vec![Ring::new(
+                [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p + Vec2::ONE),
+            )]),
+        );
+        assert!(with_hole.is_containing(&Vec2::splat(0.5)));
+        assert!(!with_hole.is_containing(&Vec2::splat(1.5)));
+        assert!(!with_hole.is_containing(&Vec2::ONE));
+    }
 }
@@ -0,0 +1,211 @@
+use bevy_math::{DVec2, DVec3, Vec2, Vec3};
+
+use crate::{
+    algorithms::convex_decomposition,
+    errors::GeometryError,
+    prelude::{DWorkplane, Workplane},
+    primitives::*,
+    Embed, Map, Point, TriangulationConfig,
+};
+
+/// Splits an area into convex pieces, e.g. for colliders or navigation meshes.
+///
+/// The pieces are found with the Hertel-Mehlhorn algorithm, which removes edges from a
+/// triangulation of the area as long as the remaining pieces stay convex. This is fast and
+/// results in at most four times as many pieces as the optimal decomposition. The triangulation is
+/// made with the given config, see [`Triangulate`](crate::Triangulate). The pieces are oriented
+/// counter-clockwise, in 3D with respect to the normal of the area.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let l_shape = Ring::new([
+///     Vec2::ZERO,
+///     Vec2::new(2.0, 0.0),
+///     Vec2::new(2.0, 1.0),
+///     Vec2::new(1.0, 1.0),
+///     Vec2::new(1.0, 2.0),
+///     Vec2::new(0.0, 2.0),
+/// ]);
+///
+/// let pieces = l_shape
+///     .to_polygon()
+///     .convex_decomposition(TriangulationConfig::default())
+///     .unwrap();
+///
+/// // the optimum is two pieces
+/// assert!((2..=3).contains(&pieces.len()));
+/// let area = pieces.iter().map(|piece| piece.area()).sum::<f32>();
+/// assert_eq!(area, 3.0);
+/// ```
+pub trait ConvexDecomposition {
+    type P: Point;
+
+    fn convex_decomposition(
+        &self,
+        config: TriangulationConfig,
+    ) -> Result<MultiRing<Self::P>, GeometryError>;
+}
+
+impl ConvexDecomposition for Polygon<DVec2> {
+    type P = DVec2;
+
+    fn convex_decomposition(
+        &self,
+        config: TriangulationConfig,
+    ) -> Result<MultiRing<DVec2>, GeometryError> {
+        convex_decomposition(self, config).map(MultiRing)
+    }
+}
+
+impl ConvexDecomposition for MultiPolygon<DVec2> {
+    type P = DVec2;
+
+    fn convex_decomposition(
+        &self,
+        config: TriangulationConfig,
+    ) -> Result<MultiRing<DVec2>, GeometryError> {
+        let mut pieces = vec![];
+        for polygon in self.iter() {
+            pieces.extend(convex_decomposition(polygon, config)?);
+        }
+        Ok(MultiRing(pieces))
+    }
+}
+
+macro_rules! impl_convex_decomposition {
+    ($($typename:ident),*) => {
+        $(
+            impl ConvexDecomposition for $typename<Vec2> {
+                type P = Vec2;
+
+                fn convex_decomposition(
+                    &self,
+                    config: TriangulationConfig,
+                ) -> Result<MultiRing<Vec2>, GeometryError> {
+                    self.map(|p| p.as_dvec2())
+                        .convex_decomposition(config)
+                        .map(|pieces| pieces.map(|p| p.as_vec2()))
+                }
+            }
+
+            impl ConvexDecomposition for $typename<Vec3> {
+                type P = Vec3;
+
+                fn convex_decomposition(
+                    &self,
+                    config: TriangulationConfig,
+                ) -> Result<MultiRing<Vec3>, GeometryError> {
+                    let workplane = Workplane::from_primitive(self)?;
+                    let injection = workplane.xy_injection();
+                    self.embed(workplane)
+                        .convex_decomposition(config)
+                        .map(|pieces| pieces.map(|p| injection.transform_point3(p.extend(0.0))))
+                }
+            }
+
+            impl ConvexDecomposition for $typename<DVec3> {
+                type P = DVec3;
+
+                fn convex_decomposition(
+                    &self,
+                    config: TriangulationConfig,
+                ) -> Result<MultiRing<DVec3>, GeometryError> {
+                    let workplane = DWorkplane::from_primitive(self)?;
+                    let injection = workplane.xy_injection();
+                    self.embed(workplane)
+                        .convex_decomposition(config)
+                        .map(|pieces| pieces.map(|p| injection.transform_point3(p.extend(0.0))))
+                }
+            }
+        )*
+    };
+}
+
+impl_convex_decomposition!(Polygon, MultiPolygon);
+
+#[cfg(test)]
+mod convex_decomposition_tests {
+    use super::*;
+    use crate::{Area, Convexity, TriangulationMethod};
+
+    #[test]
+    fn polygon_with_hole() {
+        let square = |size: f32, offset: Vec2| {
+            Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p * size + offset))
+        };
+        let polygon = Polygon::new(
+            square(4.0, Vec2::ZERO),
+            MultiRing(vec![square(2.0, Vec2::ONE)]),
+        );
+
+        let pieces = polygon
+            .convex_decomposition(TriangulationConfig::default())
+            .unwrap();
+
+        assert!(pieces.len() >= 4 && pieces.len() <= 8, "{}", pieces.len());
+        assert!(pieces.iter().all(Convexity::is_convex));
+        let area = pieces.iter().map(|piece| piece.area()).sum::<f32>();
+        assert!((area - 12.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn comb() {
+        // a comb with three teeth needs at least four pieces
+        let comb = Ring::new([
+            Vec2::new(0.0, 0.0),
+            Vec2::new(5.0, 0.0),
+            Vec2::new(5.0, 3.0),
+            Vec2::new(4.0, 3.0),
+            Vec2::new(4.0, 1.0),
+            Vec2::new(3.0, 1.0),
+            Vec2::new(3.0, 3.0),
+            Vec2::new(2.0, 3.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 3.0),
+            Vec2::new(0.0, 3.0),
+        ])
+        .to_polygon();
+
+        for method in [
+            TriangulationMethod::EarClipping,
+            TriangulationMethod::ConstrainedDelaunay,
+        ] {
+            let config = TriangulationConfig {
+                method,
+                ..Default::default()
+            };
+            let pieces = comb.convex_decomposition(config).unwrap();
+
+            assert!(pieces.len() >= 4 && pieces.len() <= 7, "{}", pieces.len());
+            assert!(pieces.iter().all(Convexity::is_convex));
+        }
+    }
+
+    #[test]
+    fn pieces_in_3d_lie_in_the_plane() {
+        let l_shape = Ring::new([
+            Vec3::ZERO,
+            Vec3::new(2.0, 0.0, 2.0),
+            Vec3::new(2.0, 1.0, 2.0),
+            Vec3::new(1.0, 1.0, 1.0),
+            Vec3::new(1.0, 2.0, 1.0),
+            Vec3::new(0.0, 2.0, 0.0),
+        ])
+        .to_polygon();
+
+        let pieces = l_shape
+            .convex_decomposition(TriangulationConfig::default())
+            .unwrap();
+
+        assert!((2..=3).contains(&pieces.len()));
+        assert!(pieces
+            .iter()
+            .flat_map(|piece| piece.points_open())
+            .all(|p| (p.x - p.z).abs() < 0.0001));
+        let area = pieces.iter().map(|piece| piece.area()).sum::<Vec3>();
+        assert!(area.abs_diff_eq(l_shape.area(), 0.0001));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    algorithms::{orient2d, Orientation},
+    primitives::*,
+    Point2,
+};
+
+/// Checks whether an area is convex, with exact orientation tests, see
+/// [`orient2d`](crate::prelude::orient2d).
+///
+/// Collinear and repeated points are allowed, but the boundary has to wind around the area exactly
+/// once in either direction. Degenerate areas without any turn and areas with holes aren't convex.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let square = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]);
+/// let with_midpoint = Ring::new([Vec2::ZERO, Vec2::X * 0.5, Vec2::X, Vec2::ONE, Vec2::Y]);
+/// let dent = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::splat(0.5), Vec2::Y]);
+///
+/// assert!(square.is_convex());
+/// assert!(square.flip().is_convex());
+/// assert!(with_midpoint.is_convex());
+/// assert!(!dent.is_convex());
+/// ```
+pub trait Convexity {
+    fn is_convex(&self) -> bool;
+}
+
+impl<P: Point2> Convexity for Ring<P> {
+    fn is_convex(&self) -> bool {
+        is_convex(self.points_open())
+    }
+}
+
+impl<P: Point2> Convexity for Triangle<P> {
+    fn is_convex(&self) -> bool {
+        is_convex(&self.0)
+    }
+}
+
+impl<P: Point2> Convexity for Polygon<P> {
+    fn is_convex(&self) -> bool {
+        self.interior().0.is_empty() && self.exterior().is_convex()
+    }
+}
+
+fn is_convex<P: Point2>(points: &[P]) -> bool {
+    let mut points = points.to_vec();
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    let n = points.len();
+    if n < 3 {
+        return false;
+    }
+
+    let mut turn = Orientation::Degenerate;
+    for i in 0..n {
+        let [prev, p, next] = [i, (i + 1) % n, (i + 2) % n].map(|j| points[j]);
+        match (turn, orient2d(prev, p, next)) {
+            (_, Orientation::Degenerate) => {}
+            (Orientation::Degenerate, found) => turn = found,
+            (turn, found) if turn != found => return false,
+            _ => {}
+        }
+    }
+
+    // turning the same way at every corner still allows going around several times, like a
+    // pentagram, or going back and forth along a line. Both change the direction along the axes
+    // more than twice.
+    let direction_changes = |coordinate: fn(P) -> P::S| {
+        let directions = (0..n)
+            .map(|i| coordinate(points[(i + 1) % n]).partial_cmp(&coordinate(points[i])))
+            .filter(|direction| *direction != Some(std::cmp::Ordering::Equal))
+            .collect::<Vec<_>>();
+        (0..directions.len())
+            .filter(|i| directions[*i] != directions[(i + 1) % directions.len()])
+            .count()
+    };
+    turn != Orientation::Degenerate && direction_changes(P::x) <= 2 && direction_changes(P::y) <= 2
+}
+
+#[cfg(test)]
+mod convexity_tests {
+    use bevy_math::{DVec2, Vec2};
+
+    use super::*;
+
+    #[test]
+    fn degenerate_and_self_overlapping_rings() {
+        let pentagram = Ring::new(
+            [0, 2, 4, 1, 3].map(|i| DVec2::from_angle(i as f64 * std::f64::consts::TAU / 5.0)),
+        );
+        assert!(!pentagram.is_convex());
+
+        let line = Ring::new([Vec2::ZERO, Vec2::X, Vec2::X * 2.0]);
+        assert!(!line.is_convex());
+
+        // a spike going back along a straight edge
+        let spike = Ring::new([Vec2::ZERO, Vec2::X * 2.0, Vec2::X, Vec2::ONE, Vec2::Y]);
+        assert!(!spike.is_convex());
+
+        let repeated = Ring::new([
+            Vec2::ZERO,
+            Vec2::ZERO,
+            Vec2::X,
+            Vec2::ONE,
+            Vec2::ONE,
+            Vec2::Y,
+        ]);
+        assert!(repeated.is_convex());
+        assert!(!Triangle([Vec2::ZERO, Vec2::X, Vec2::X * 3.0]).is_convex());
+        assert!(Triangle([Vec2::ZERO, Vec2::Y, Vec2::X]).is_convex());
+    }
+
+    #[test]
+    fn nearly_straight_corner() {
+        // the last corner lies on the diagonal, or bends inwards by a single unit in the last place
+        let [origin, x, far] = [DVec2::ZERO, DVec2::X, DVec2::splat(1e6)];
+        let on_diagonal = DVec2::splat(0.5);
+        let dent = on_diagonal.with_y(f64::from_bits(0.5f64.to_bits() - 1));
+        let bulge = on_diagonal.with_y(f64::from_bits(0.5f64.to_bits() + 1));
+        assert!(Ring::new([origin, x, far, on_diagonal]).is_convex());
+        assert!(!Ring::new([origin, x, far, dent]).is_convex());
+        assert!(Ring::new([origin, x, far, bulge]).is_convex());
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{prelude::Line2DIntersection, LinesIter, Point2};
+use crate::{
+    prelude::{Line2DIntersection, Line3DIntersection},
+    Line, LinesIter, Point2, Point3,
+};
 
 /// Check if the lines of this kind of geometry are intersecting with any of the lines of the other geometry
 ///
@@ -27,6 +30,10 @@ use crate::{prelude::Line2DIntersection, LinesIter, Point2};
 /// assert!(intersection_points.contains(&Vec2::new(0.0, 1.0)));
 /// ```
 pub trait LineIntersectable<P: Point2, Other: LinesIter<P = P>>: LinesIter<P = P> {
+    /// All pairs of lines which intersect according to [`Line::intersection`], ordered by the
+    /// line of `self` and then by the line of `other`. Every line of `self` is compared with every
+    /// line of `other`, for geometries with many lines see
+    /// [`sweep_intersections`](crate::prelude::sweep_intersections).
     fn line_intersections(
         &self,
         other: &Other,
@@ -97,6 +104,88 @@ where
     }
 }
 
+/// Check where the lines of this kind of geometry in 3D meet the lines of the other geometry, see
+/// [`Line::intersection_3d`]
+///
+/// # Example
+///
+/// ```
+/// use selo::prelude::*;
+///
+/// // the outline of a wall and a beam passing through it
+/// let wall = Ring::new([Vec3::ZERO, Vec3::X * 4.0, Vec3::new(4.0, 0.0, 3.0), Vec3::Z * 3.0]);
+/// let beam = LineString::new(vec![Vec3::new(1.0, -1.0, 3.0), Vec3::new(1.0, 1.0, 3.0)]);
+///
+/// let points = wall.intersection_points_3d(&beam, 0.001).collect::<Vec<_>>();
+///
+/// assert_eq!(points, [Vec3::new(1.0, 0.0, 3.0)]);
+/// let floor_beam = LineString::new(vec![Vec3::new(1.0, -1.0, 0.5), Vec3::new(1.0, 1.0, 0.5)]);
+/// assert!(wall.intersection_points_3d(&floor_beam, 0.001).next().is_none());
+/// let [on_wall, on_beam] = wall.closest_approach(&floor_beam).unwrap().0;
+/// assert_eq!((on_wall, on_beam), (Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.5)));
+/// ```
+pub trait LineIntersectable3d<P: Point3, Other: LinesIter<P = P>>: LinesIter<P = P> {
+    /// All pairs of lines which intersect
+    fn line_intersections_3d(
+        &self,
+        other: &Other,
+        tolerance: P::S,
+    ) -> impl Iterator<Item = Line3DIntersection<P>>;
+
+    /// The points where the lines cross each other, without the ones where they just touch
+    fn intersection_points_3d(&self, other: &Other, tolerance: P::S) -> impl Iterator<Item = P> {
+        self.line_intersections_3d(other, tolerance)
+            .filter_map(|intersection| match intersection {
+                Line3DIntersection::Coplanar(intersection) => intersection
+                    .is_true_intersection()
+                    .then(|| intersection.pos())
+                    .flatten(),
+                Line3DIntersection::Skew(_) => None,
+            })
+    }
+
+    /// The shortest connection between the lines of both geometries, `None` if either has no
+    /// lines. The connection starts on `self` and ends on `other`.
+    fn closest_approach(&self, other: &Other) -> Option<Line<P>>;
+}
+
+impl<P: Point3, SelfT, OtherT> LineIntersectable3d<P, OtherT> for SelfT
+where
+    SelfT: LinesIter<P = P>,
+    OtherT: LinesIter<P = P>,
+{
+    fn line_intersections_3d(
+        &self,
+        other: &OtherT,
+        tolerance: P::S,
+    ) -> impl Iterator<Item = Line3DIntersection<P>> {
+        self.iter_lines()
+            .flat_map(move |self_line| {
+                other
+                    .iter_lines()
+                    .map(move |other_line| self_line.intersection_3d(&other_line, tolerance))
+            })
+            .filter(|intersection| intersection.intersect())
+    }
+
+    fn closest_approach(&self, other: &OtherT) -> Option<Line<P>> {
+        self.iter_lines()
+            .flat_map(|self_line| {
+                other
+                    .iter_lines()
+                    .map(move |other_line| Line(self_line.closest_points(&other_line)))
+            })
+            .fold(None, |closest: Option<(Line<P>, P::S)>, line| {
+                let length = line.length();
+                match closest {
+                    Some((_, shortest)) if shortest <= length => closest,
+                    _ => Some((line, length)),
+                }
+            })
+            .map(|(line, _)| line)
+    }
+}
+
 #[cfg(test)]
 mod line_intersection_tests {
     use super::*;
@@ -114,4 +203,124 @@ mod line_intersection_tests {
         assert!(intersections.contains(&Vec2::new(1.0, 0.5)));
         assert!(intersections.contains(&Vec2::new(0.5, 1.0)));
     }
+
+    #[test]
+    fn lines_in_3d() {
+        let a = Line([DVec3::ZERO, DVec3::new(2.0, 2.0, 2.0)]);
+
+        // collinear and overlapping
+        let b = Line([DVec3::ONE, DVec3::splat(3.0)]);
+        let Line3DIntersection::Coplanar(Line2DIntersection::CollinearOverlap(overlap)) =
+            a.intersection_3d(&b, 1e-9)
+        else {
+            panic!("the lines overlap");
+        };
+        assert!(overlap.src().abs_diff_eq(DVec3::ONE, 1e-9));
+        assert!(overlap.dst().abs_diff_eq(DVec3::splat(2.0), 1e-9));
+        assert_eq!(a.skew_distance(&b), 0.0);
+
+        // touching at an endpoint, so it's no true intersection
+        let c = Line([DVec3::splat(2.0), DVec3::new(2.0, 2.0, 5.0)]);
+        let intersection = a.intersection_3d(&c, 1e-9);
+        assert!(intersection.intersect());
+        assert!(intersection
+            .pos()
+            .unwrap()
+            .abs_diff_eq(DVec3::splat(2.0), 1e-9));
+        assert_eq!(a.intersection_points_3d(&c, 1e-9).count(), 0);
+
+        // parallel, shifted sideways
+        let d = Line(a.0.map(|p| p + DVec3::X));
+        assert!((a.skew_distance(&d) - (2.0f64 / 3.0).sqrt()).abs() < 1e-9);
+        assert!(matches!(
+            a.intersection_3d(&d, 1e-9),
+            Line3DIntersection::Coplanar(Line2DIntersection::ParallelNonCollinear)
+        ));
+
+        // skew, within the tolerance only for the larger one
+        let e = Line([DVec3::new(0.0, 2.0, 1.001), DVec3::new(2.0, 0.0, 1.001)]);
+        let Line3DIntersection::Skew(connection) = a.intersection_3d(&e, 1e-4) else {
+            panic!("the lines are skew");
+        };
+        assert!((connection.length() - a.segment_distance(&e)).abs() < 1e-12);
+        assert!(a.intersection_3d(&e, 1e-2).pos().is_some());
+
+        // a collinear overlap shorter than the tolerance touches where it is
+        let long = Line([DVec3::ZERO, DVec3::X * 10.0]);
+        let tiny = Line([DVec3::X * 8.0, DVec3::X * 8.00001]);
+        for intersection in [
+            long.intersection_3d(&tiny, 1e-3),
+            tiny.intersection_3d(&long, 1e-3),
+        ] {
+            let Line3DIntersection::Coplanar(Line2DIntersection::CollinearTouch(touch)) =
+                intersection
+            else {
+                panic!("the lines touch");
+            };
+            assert!(touch.abs_diff_eq(DVec3::X * 8.0, 1e-4), "{touch}");
+        }
+    }
+
+    #[test]
+    fn exact_classification_without_tolerance() {
+        // an endpoint on a long line far from the origin, where the scalars round
+        let long = Line([DVec2::new(0.7, 0.3), DVec2::new(3e7 + 0.7, 1e7 + 0.3)]);
+        let touching = Line([DVec2::new(0.0, 1e7), DVec2::new(3e6 + 0.7, 1e6 + 0.3)]);
+        let Line2DIntersection::Simple(pos, on_long, on_touching) =
+            long.intersection(&touching, 0.0)
+        else {
+            panic!("the lines aren't parallel");
+        };
+        assert!(on_long.touches_linesegment());
+        assert_eq!(on_touching.scalar(), 1.0);
+        assert_eq!(pos, touching.dst());
+
+        // collinear far away from the origin, with a determinant which doesn't round to zero
+        let a = Line([DVec2::new(0.1, 0.1), DVec2::new(0.7, 0.7)]);
+        let b = Line([DVec2::new(0.3, 0.3), DVec2::new(1e9, 1e9)]);
+        assert!(matches!(
+            a.intersection(&b, 0.0),
+            Line2DIntersection::CollinearOverlap(_)
+        ));
+    }
+
+    #[test]
+    fn collinear_overlap_shorter_than_the_tolerance() {
+        let long = Line([DVec2::ZERO, DVec2::X * 10.0]);
+        let tiny = Line([DVec2::X * 8.0, DVec2::X * 8.00001]);
+
+        for intersection in [
+            long.intersection(&tiny, 1e-3),
+            tiny.intersection(&long, 1e-3),
+        ] {
+            let Line2DIntersection::CollinearTouch(pos) = intersection else {
+                panic!("{intersection:?}");
+            };
+            assert!(pos.abs_diff_eq(tiny.center(), 1e-9), "{pos}");
+        }
+    }
+
+    #[test]
+    fn exact_classification_of_near_misses() {
+        // the touching line of `exact_classification_without_tolerance`, ending two units in the
+        // last place above or below the long line, where its scalar still rounds to one
+        let long = Line([DVec2::new(0.7, 0.3), DVec2::new(3e7 + 0.7, 1e7 + 0.3)]);
+        let end = |ulps: i64| {
+            let y = f64::from_bits(((1e6f64 + 0.3).to_bits() as i64 + ulps) as u64);
+            Line([DVec2::new(0.0, 1e7), DVec2::new(3e6 + 0.7, y)])
+        };
+
+        let short = long.intersection(&end(2), 0.0);
+        let Line2DIntersection::Simple(_, _, on_short) = short else {
+            panic!("the lines aren't parallel");
+        };
+        assert!(matches!(
+            on_short,
+            crate::prelude::Line2DIntersectionKind::OutsideDst(_)
+        ));
+        assert!(!short.intersect());
+
+        // crossing the long line just before its end
+        assert!(long.intersection(&end(-2), 0.0).intersect());
+    }
 }
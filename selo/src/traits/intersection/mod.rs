@@ -1,2 +1,5 @@
 mod lines;
 pub use lines::*;
+
+mod self_intersections;
+pub use self_intersections::*;
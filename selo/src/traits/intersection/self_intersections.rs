@@ -0,0 +1,83 @@
+use crate::{
+    prelude::{sweep_intersections, Line2DIntersection, SweepIntersection},
+    LinesIter, Point2,
+};
+
+/// Find the places where the lines of a geometry cross or touch each other, e.g. to validate
+/// imported plans. Uses [`sweep_intersections`], so it scales to large geometries.
+///
+/// Consecutive lines meeting at their shared point aren't reported, neither are the first and the
+/// last line of a closed chain of lines like a ring. Consecutive lines which overlap, i.e. spikes,
+/// are reported.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let square = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]);
+/// let bow_tie = Ring::new([Vec2::ZERO, Vec2::ONE, Vec2::X, Vec2::Y]);
+///
+/// assert!(square.is_simple(0.0001));
+/// assert!(!bow_tie.is_simple(0.0001));
+///
+/// let intersections = bow_tie.self_intersections(0.0001);
+/// assert_eq!(intersections.len(), 1);
+/// assert_eq!(intersections[0].intersection.pos(), Some(Vec2::splat(0.5)));
+/// ```
+pub trait SelfIntersections: LinesIter
+where
+    Self::P: Point2,
+{
+    /// The intersections of the lines with each other, the geometry index of the
+    /// [`SweepIntersection`] is always zero
+    fn self_intersections(
+        &self,
+        tolerance: <Self::P as crate::Point>::S,
+    ) -> Vec<SweepIntersection<Self::P>>;
+
+    /// Whether none of the lines touches or crosses another one, except for the neighbouring ones
+    /// at their shared point
+    fn is_simple(&self, tolerance: <Self::P as crate::Point>::S) -> bool {
+        self.self_intersections(tolerance).is_empty()
+    }
+}
+
+impl<T: LinesIter> SelfIntersections for T
+where
+    T::P: Point2,
+{
+    fn self_intersections(
+        &self,
+        tolerance: <T::P as crate::Point>::S,
+    ) -> Vec<SweepIntersection<T::P>> {
+        let lines = self.iter_lines().collect::<Vec<_>>();
+        let follows = |i: usize, j: usize| lines[i].dst() == lines[j].src();
+        // the first and the last line of the chain of consecutive lines each line belongs to
+        let mut chains = vec![(0, 0); lines.len()];
+        for i in 1..lines.len() {
+            chains[i].0 = if follows(i - 1, i) {
+                chains[i - 1].0
+            } else {
+                i
+            };
+        }
+        for i in (0..lines.len()).rev() {
+            chains[i].1 = match chains.get(i + 1) {
+                Some(next) if follows(i, i + 1) => next.1,
+                _ => i,
+            };
+        }
+        let neighbours = |i: usize, j: usize| {
+            (j == i + 1 && follows(i, j)) || (chains[i] == (i, j) && follows(j, i))
+        };
+
+        sweep_intersections(std::slice::from_ref(self), tolerance)
+            .into_iter()
+            .filter(|found| {
+                let [(_, i), (_, j)] = found.lines;
+                !neighbours(i, j)
+                    || matches!(found.intersection, Line2DIntersection::CollinearOverlap(_))
+            })
+            .collect()
+    }
+}
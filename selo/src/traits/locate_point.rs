@@ -0,0 +1,290 @@
+use num_traits::Float;
+
+use crate::{
+    algorithms::{on_segment, winding_number},
+    primitives::*,
+    ContainsGeometry, LinesIter, Point, Point2,
+};
+
+/// Where a point lies relative to an area, see [`LocatePoint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointLocation {
+    Inside,
+    /// On the boundary, with the index of the closest boundary line in the order of
+    /// [`LinesIter::iter_lines`]
+    OnBoundary(usize),
+    Outside,
+}
+
+/// Locates points relative to an area, without converting it to other geometry types.
+///
+/// Points closer than `tolerance` to the boundary are on the boundary, all other points are
+/// classified exactly, see [`orient2d`](crate::prelude::orient2d). Every ring is an area on its
+/// own and follows the non-zero rule: a point is inside a ring if the
+/// [`winding number`](LocatePoint::winding_number) of that ring around it isn't zero, also for
+/// rings which cross themselves or wind around the point several times. The areas of the rings
+/// of a [`MultiRing`] are joined, like for [`ContainsGeometry`], and holes exclude their inside,
+/// both regardless of the orientation of the rings. So a point can be inside although the summed
+/// winding number of all rings is zero. 3D points are located with respect to
+/// the workplane of a [`FlatPrimitive`](crate::FlatPrimitive) and are outside if they're farther
+/// than `tolerance` away from it, their winding number is the one of their projection onto it.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let room = Polygon::new(
+///     Ring::new([Vec2::ZERO, Vec2::X * 4.0, Vec2::new(4.0, 3.0), Vec2::Y * 3.0]),
+///     MultiRing(vec![Ring::new([
+///         Vec2::new(1.0, 1.0),
+///         Vec2::new(1.0, 2.0),
+///         Vec2::new(2.0, 2.0),
+///         Vec2::new(2.0, 1.0),
+///     ])]),
+/// );
+///
+/// assert_eq!(room.locate_point(Vec2::new(3.0, 1.0), 0.001), PointLocation::Inside);
+/// assert_eq!(room.locate_point(Vec2::new(4.0005, 1.0), 0.001), PointLocation::OnBoundary(1));
+/// assert_eq!(room.locate_point(Vec2::new(1.5, 1.5), 0.001), PointLocation::Outside);
+/// // the first line of the hole follows the four lines of the exterior
+/// assert_eq!(room.locate_point(Vec2::new(1.0, 1.5), 0.0), PointLocation::OnBoundary(4));
+///
+/// // a column on the first floor, in 3D
+/// let floor = Ring::new([Vec3::ZERO, Vec3::X * 4.0, Vec3::new(4.0, 3.0, 0.0), Vec3::Y * 3.0]);
+/// let plane = Workplane::from_primitive(&floor).unwrap();
+/// let floor = FlatPrimitive::<Ring<Vec2>>::new(floor, plane);
+/// assert_eq!(floor.locate_point(Vec3::new(1.0, 1.0, 0.0), 0.001), PointLocation::Inside);
+/// assert_eq!(floor.locate_point(Vec3::new(1.0, 1.0, 0.1), 0.001), PointLocation::Outside);
+/// ```
+pub trait LocatePoint {
+    type P: Point;
+
+    fn locate_point(&self, p: Self::P, tolerance: <Self::P as Point>::S) -> PointLocation;
+
+    /// How often the boundary winds counter-clockwise around the point, clockwise windings count
+    /// negative. The windings of all rings are summed up, so properly oriented holes cancel out
+    /// the exterior. Returns `None` if the point lies exactly on the boundary.
+    fn winding_number(&self, p: Self::P) -> Option<i32>;
+}
+
+/// The index of the boundary line closest to the point, if it's within the tolerance. Points
+/// exactly on a line are always found, regardless of rounding in the distances.
+fn boundary_line<P: Point2>(
+    lines: impl Iterator<Item = Line<P>>,
+    p: P,
+    tolerance: P::S,
+) -> Option<usize> {
+    lines
+        .enumerate()
+        .filter_map(|(i, line)| {
+            if on_segment(line.src(), line.dst(), p) {
+                return Some((i, <P::S>::from(0.0)));
+            }
+            let t = line
+                .scalar_of(p)
+                .max(<P::S>::from(0.0))
+                .min(<P::S>::from(1.0));
+            let distance = (p - line.pos_scaled(t)).norm();
+            (distance <= tolerance).then_some((i, distance))
+        })
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+}
+
+fn locate<T: LinesIter<P = P>, P: Point2>(
+    geometry: &T,
+    p: P,
+    tolerance: P::S,
+    inside: impl FnOnce() -> bool,
+) -> PointLocation {
+    match boundary_line(geometry.iter_lines(), p, tolerance) {
+        Some(i) => PointLocation::OnBoundary(i),
+        None if inside() => PointLocation::Inside,
+        None => PointLocation::Outside,
+    }
+}
+
+/// Whether the open points of a ring wind around a point which isn't on any boundary
+fn winds_around<P: Point2>(ring: &[P], p: P) -> bool {
+    winding_number(ring, p).is_some_and(|winding| winding != 0)
+}
+
+/// Whether a point which isn't on any boundary is inside the exterior and outside of all holes
+fn inside_polygon<P: Point2>(polygon: &Polygon<P>, p: P) -> bool {
+    winds_around(polygon.exterior().points_open(), p)
+        && !polygon
+            .interior()
+            .iter()
+            .any(|hole| winds_around(hole.points_open(), p))
+}
+
+impl<P: Point2> LocatePoint for Triangle<P> {
+    type P = P;
+
+    fn locate_point(&self, p: P, tolerance: P::S) -> PointLocation {
+        locate(self, p, tolerance, || winds_around(&self.0, p))
+    }
+
+    fn winding_number(&self, p: P) -> Option<i32> {
+        winding_number(&self.0, p)
+    }
+}
+
+impl<P: Point2> LocatePoint for Ring<P> {
+    type P = P;
+
+    fn locate_point(&self, p: P, tolerance: P::S) -> PointLocation {
+        locate(self, p, tolerance, || winds_around(self.points_open(), p))
+    }
+
+    fn winding_number(&self, p: P) -> Option<i32> {
+        winding_number(self.points_open(), p)
+    }
+}
+
+impl<P: Point2> LocatePoint for MultiRing<P> {
+    type P = P;
+
+    fn locate_point(&self, p: P, tolerance: P::S) -> PointLocation {
+        locate(self, p, tolerance, || {
+            self.iter().any(|ring| winds_around(ring.points_open(), p))
+        })
+    }
+
+    fn winding_number(&self, p: P) -> Option<i32> {
+        self.iter().map(|ring| ring.winding_number(p)).sum()
+    }
+}
+
+impl<P: Point2> LocatePoint for Polygon<P> {
+    type P = P;
+
+    fn locate_point(&self, p: P, tolerance: P::S) -> PointLocation {
+        locate(self, p, tolerance, || inside_polygon(self, p))
+    }
+
+    fn winding_number(&self, p: P) -> Option<i32> {
+        self.iter_rings().map(|ring| ring.winding_number(p)).sum()
+    }
+}
+
+impl<P: Point2> LocatePoint for MultiPolygon<P> {
+    type P = P;
+
+    fn locate_point(&self, p: P, tolerance: P::S) -> PointLocation {
+        locate(self, p, tolerance, || {
+            self.iter().any(|polygon| inside_polygon(polygon, p))
+        })
+    }
+
+    fn winding_number(&self, p: P) -> Option<i32> {
+        self.iter().map(|polygon| polygon.winding_number(p)).sum()
+    }
+}
+
+// Points are located directly, the boundary doesn't count as contained
+
+macro_rules! impl_contains_point {
+    ($($typename:ident),*) => {
+        $(
+            impl<P: Point2> ContainsGeometry<P> for $typename<P> {
+                type Rhs = P;
+                fn is_containing(&self, rhs: &P) -> bool {
+                    self.locate_point(*rhs, P::S::from(0.0)) == PointLocation::Inside
+                }
+            }
+        )*
+    };
+}
+
+impl_contains_point!(Triangle, Ring, MultiRing, Polygon, MultiPolygon);
+
+#[cfg(test)]
+mod locate_point_tests {
+    use bevy_math::DVec2;
+
+    use super::*;
+    use crate::Flip;
+
+    #[test]
+    fn self_overlapping_ring() {
+        // a ring going around its center twice, like a spiral closing onto itself
+        let twice = Ring::new(
+            (0..9)
+                .map(|i| DVec2::from_angle(i as f64 * std::f64::consts::TAU * 2.0 / 9.0))
+                .collect::<Vec<_>>(),
+        );
+        let bow_tie = Ring::new([DVec2::ZERO, DVec2::ONE, DVec2::X, DVec2::Y]);
+
+        assert_eq!(twice.winding_number(DVec2::ZERO), Some(2));
+        assert_eq!(twice.locate_point(DVec2::ZERO, 1e-9), PointLocation::Inside);
+        assert_eq!(
+            twice.locate_point(DVec2::splat(2.0), 1e-9),
+            PointLocation::Outside
+        );
+        // the halves of the bow tie wind in opposite directions
+        assert_eq!(bow_tie.winding_number(DVec2::new(0.75, 0.5)), Some(-1));
+        assert_eq!(bow_tie.winding_number(DVec2::new(0.25, 0.5)), Some(1));
+        assert_eq!(bow_tie.winding_number(DVec2::splat(0.5)), None);
+        assert_eq!(
+            bow_tie.locate_point(DVec2::new(0.75, 0.5), 1e-9),
+            PointLocation::Inside
+        );
+        // the crossing is on the boundary, the closest line is the first one found there
+        assert_eq!(
+            bow_tie.locate_point(DVec2::splat(0.5), 0.0),
+            PointLocation::OnBoundary(0)
+        );
+    }
+
+    #[test]
+    fn boundary_of_multi_polygons() {
+        let square = |offset: DVec2| {
+            Ring::new([DVec2::ZERO, DVec2::X, DVec2::ONE, DVec2::Y].map(|p| p + offset))
+                .to_polygon()
+        };
+        let squares = MultiPolygon(vec![square(DVec2::ZERO), square(DVec2::X * 2.0)]);
+
+        assert_eq!(
+            squares.locate_point(DVec2::new(2.5, 0.5), 0.1),
+            PointLocation::Inside
+        );
+        assert_eq!(
+            squares.locate_point(DVec2::new(1.5, 0.5), 0.1),
+            PointLocation::Outside
+        );
+        // the closest of the boundary lines within the tolerance
+        assert_eq!(
+            squares.locate_point(DVec2::new(1.6, 0.5), 0.5),
+            PointLocation::OnBoundary(7)
+        );
+        assert_eq!(
+            squares.locate_point(DVec2::new(3.0, 1.0), 0.0),
+            PointLocation::OnBoundary(5)
+        );
+        assert_eq!(squares.winding_number(DVec2::new(2.5, 0.5)), Some(1));
+        assert_eq!(squares.winding_number(DVec2::new(2.0, 0.5)), None);
+    }
+
+    #[test]
+    fn overlapping_rings_are_joined() {
+        let square = |offset: DVec2| {
+            Ring::new([DVec2::ZERO, DVec2::X, DVec2::ONE, DVec2::Y].map(|p| p * 2.0 + offset))
+        };
+        // the second square winds the other way, the windings cancel out where they overlap
+        let rings = MultiRing(vec![square(DVec2::ZERO), square(DVec2::ONE).flip()]);
+        let overlap = DVec2::splat(1.5);
+
+        assert_eq!(rings.winding_number(overlap), Some(0));
+        assert_eq!(rings.locate_point(overlap, 1e-9), PointLocation::Inside);
+        assert!(rings.is_containing(&overlap));
+        assert_eq!(
+            rings.locate_point(DVec2::splat(2.5), 1e-9),
+            PointLocation::Inside
+        );
+        assert_eq!(
+            rings.locate_point(DVec2::new(0.5, 2.5), 1e-9),
+            PointLocation::Outside
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    Geometry, Line, LineString, MultiLineString, MultiPolygon, MultiRing, Point, Polygon, Ring,
-    Triangle,
+    Geometry, Line, LineString, MultiLineString, MultiPolygon, MultiRing, MultiTriangle, Point,
+    Polygon, Ring, Triangle,
 };
 
 use super::IterPoints;
@@ -48,6 +48,15 @@ impl<PIn: Point, POut: Point> Map<PIn, POut> for Triangle<PIn> {
     }
 }
 
+impl<PIn: Point, POut: Point> Map<PIn, POut> for MultiTriangle<PIn> {
+    type Output = MultiTriangle<POut>;
+
+    #[inline]
+    fn map(&self, mut f: impl FnMut(PIn) -> POut) -> MultiTriangle<POut> {
+        MultiTriangle(self.0.iter().map(|t| t.map(&mut f)).collect())
+    }
+}
+
 impl<PIn: Point, POut: Point> Map<PIn, POut> for LineString<PIn> {
     type Output = LineString<POut>;
 
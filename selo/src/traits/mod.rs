@@ -1,6 +1,9 @@
 mod boolops;
 pub use boolops::*;
 
+mod clip;
+pub use clip::*;
+
 mod area;
 pub use area::*;
 
@@ -25,9 +28,48 @@ pub use flip::*;
 mod buffer;
 pub use buffer::*;
 
+mod buffer_style;
+pub use buffer_style::*;
+
+mod offset_curve;
+pub use offset_curve::*;
+
+mod stitch;
+pub use stitch::*;
+
+mod straight_skeleton;
+pub use straight_skeleton::*;
+
+mod roof;
+pub use roof::*;
+
+mod triangulate;
+pub use triangulate::*;
+
+mod voronoi;
+pub use voronoi::*;
+
+mod convex_decomposition;
+pub use convex_decomposition::*;
+
+mod convexity;
+pub use convexity::*;
+
+mod partition;
+pub use partition::*;
+
+mod split;
+pub use split::*;
+
+mod section;
+pub use section::*;
+
 mod contains;
 pub use contains::*;
 
+mod locate_point;
+pub use locate_point::*;
+
 mod intersection;
 pub use intersection::*;
 
@@ -39,3 +81,6 @@ pub use dedup_points::*;
 
 mod orient;
 pub use orient::*;
+
+mod planarity;
+pub use planarity::*;
@@ -0,0 +1,442 @@
+use std::f64::consts::{PI, TAU};
+
+use bevy_math::{DVec2, DVec3, Vec2, Vec3};
+
+use crate::{
+    prelude::{DWorkplane, Workplane, WorkplanePoint},
+    primitives::*,
+    Embed, Map, Point, Unembed,
+};
+
+use super::{BufferStyle, JoinStyle};
+
+/// One-sided parallel offset of open lines.
+///
+/// Every point of the resulting lines has the distance `distance.abs()` to the original lines. A
+/// positive `distance` offsets to the left of the lines (with respect to their direction), a
+/// negative `distance` to the right. Parts of the naive offset which come closer to the original
+/// lines than the distance (e.g. loops at tight inner corners) are removed, so the result may
+/// consist of several [`LineString`]s. The direction of the result matches the original lines.
+///
+/// The outer corners are shaped by `style.join`, `style.cap` has no effect. Closed
+/// [`LineString`]s are treated as open ones, use [`BufferGeometry`](crate::BufferGeometry) to
+/// offset areas.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let path = LineString::new(vec![Vec2::ZERO, Vec2::X * 2.0, Vec2::new(2.0, 2.0)]);
+///
+/// let left = path.offset_curve(0.5, BufferStyle::default());
+///
+/// assert_eq!(
+///     left.0,
+///     vec![LineString::new(vec![Vec2::new(0.0, 0.5), Vec2::new(1.5, 0.5), Vec2::new(1.5, 2.0)])]
+/// );
+/// ```
+pub trait OffsetCurve {
+    type P: Point;
+
+    fn offset_curve(&self, distance: f64, style: BufferStyle) -> MultiLineString<Self::P>;
+}
+
+/// One-sided parallel offset of open lines in 3D space. The lines are offset within the given
+/// [`Workplane`], or [`DWorkplane`] for `f64` points, and left/right is determined with respect to
+/// the normal of the workplane. See [`OffsetCurve`] for details.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let wall = Line([Vec3::ZERO, Vec3::X]);
+/// let floor = Workplane::from_normal_and_origin(Dir3::Z, Vec3::ZERO);
+///
+/// let face = wall.offset_curve_in(floor, 0.5, BufferStyle::default());
+///
+/// assert!(face.0[0].0[0].abs_diff_eq(Vec3::Y * 0.5, 0.0001));
+/// ```
+pub trait OffsetCurve3d {
+    type P: WorkplanePoint;
+
+    fn offset_curve_in(
+        &self,
+        workplane: <Self::P as WorkplanePoint>::Workplane,
+        distance: f64,
+        style: BufferStyle,
+    ) -> MultiLineString<Self::P>;
+}
+
+impl OffsetCurve for LineString<DVec2> {
+    type P = DVec2;
+
+    fn offset_curve(&self, distance: f64, style: BufferStyle) -> MultiLineString<DVec2> {
+        offset_polyline(&self.0, distance, style)
+    }
+}
+
+impl OffsetCurve for Line<DVec2> {
+    type P = DVec2;
+
+    fn offset_curve(&self, distance: f64, style: BufferStyle) -> MultiLineString<DVec2> {
+        offset_polyline(&self.0, distance, style)
+    }
+}
+
+impl OffsetCurve for MultiLineString<DVec2> {
+    type P = DVec2;
+
+    fn offset_curve(&self, distance: f64, style: BufferStyle) -> MultiLineString<DVec2> {
+        MultiLineString(
+            self.0
+                .iter()
+                .flat_map(|linestring| linestring.offset_curve(distance, style).0)
+                .collect(),
+        )
+    }
+}
+
+// f32 geometry is offset in f64 precision, just like with `BufferGeometry`
+macro_rules! impl_offset_curve_f32 {
+    ($($typename:ty),*) => {
+        $(
+            impl OffsetCurve for $typename {
+                type P = Vec2;
+
+                fn offset_curve(&self, distance: f64, style: BufferStyle) -> MultiLineString<Vec2> {
+                    self.map(|p: Vec2| p.as_dvec2())
+                        .offset_curve(distance, style)
+                        .map(|p| p.as_vec2())
+                }
+            }
+        )*
+    };
+}
+
+impl_offset_curve_f32!(Line<Vec2>, LineString<Vec2>, MultiLineString<Vec2>);
+
+macro_rules! impl_offset_curve_3d {
+    ($($typename:ident),*) => {
+        $(
+            impl_offset_curve_3d!(@point $typename; Vec3; Workplane);
+            impl_offset_curve_3d!(@point $typename; DVec3; DWorkplane);
+        )*
+    };
+    (@point $typename:ident; $point:ty; $workplane:ty) => {
+        impl OffsetCurve3d for $typename<$point> {
+            type P = $point;
+
+            fn offset_curve_in(
+                &self,
+                workplane: $workplane,
+                distance: f64,
+                style: BufferStyle,
+            ) -> MultiLineString<$point> {
+                self.embed(workplane)
+                    .offset_curve(distance, style)
+                    .unembed(workplane)
+            }
+        }
+    };
+}
+
+impl_offset_curve_3d!(Line, LineString, MultiLineString);
+
+/// Where a segment of the naive offset comes from. This determines which lines of the original
+/// geometry are ignored when checking whether the segment keeps its distance.
+#[derive(Debug, Clone, Copy)]
+enum Source {
+    /// Parallel to the `n`th line of the original, which is by construction at the exact distance
+    Line(usize),
+    /// Outer corner at the `n`th point of the original, which is at the exact distance to both
+    /// adjacent lines
+    OuterCorner(usize),
+    /// Connection at an inner corner which always needs to be checked against all lines
+    InnerCorner,
+}
+
+fn offset_polyline(points: &[DVec2], distance: f64, style: BufferStyle) -> MultiLineString<DVec2> {
+    let mut points = points.to_vec();
+    points.dedup();
+    if points.len() < 2 || !distance.is_finite() {
+        return MultiLineString::default();
+    }
+    if distance == 0.0 {
+        return MultiLineString(vec![LineString::new(points)]);
+    }
+
+    let lines = points
+        .windows(2)
+        .map(|w| Line([w[0], w[1]]))
+        .collect::<Vec<_>>();
+    let raw = naive_offset(&lines, distance, style);
+
+    // split the naive offset at all of its self intersections
+    let mut splits = vec![vec![]; raw.len()];
+    for i in 0..raw.len() {
+        for j in i + 2..raw.len() {
+            if let Some((t, u)) = segment_intersection(raw[i].0 .0, raw[j].0 .0) {
+                // both parts need the exact same point to be joined again later
+                let p = raw[i].0.pos_scaled(t);
+                splits[i].push((t, p));
+                splits[j].push((u, p));
+            }
+        }
+    }
+
+    // keep only the pieces which keep the distance to the original lines
+    let tolerance = distance.abs() * 1e-6;
+    let mut result = vec![];
+    let mut current: Vec<DVec2> = vec![];
+    for ((line, source), mut ts) in raw.into_iter().zip(splits) {
+        ts.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        let split_points = std::iter::once(line.src())
+            .chain(ts.into_iter().map(|(_, p)| p))
+            .chain(std::iter::once(line.dst()))
+            .collect::<Vec<_>>();
+        for piece in split_points.windows(2) {
+            let (a, b) = (piece[0], piece[1]);
+            let mid = (a + b) * 0.5;
+            let keeps_distance = lines
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| match source {
+                    Source::Line(j) => *i != j,
+                    Source::OuterCorner(j) => *i + 1 != j && *i != j,
+                    Source::InnerCorner => true,
+                })
+                .all(|(_, line)| distance_to_line(*line, mid) >= distance.abs() - tolerance);
+            // removed loops start and end at the same point, so the valid parts around them are
+            // joined again
+            if keeps_distance {
+                if current.last() != Some(&a) {
+                    result.push(std::mem::take(&mut current));
+                    current.push(a);
+                }
+                // the parts of a split line and the corners are often collinear
+                if let [.., prev, last] = current[..] {
+                    let collinear = (last - prev).perp_dot(b - last).abs()
+                        <= tolerance * (last - prev).length()
+                        && (last - prev).dot(b - last) > 0.0;
+                    if collinear {
+                        current.pop();
+                    }
+                }
+                current.push(b);
+            }
+        }
+    }
+    result.push(current);
+
+    MultiLineString(
+        result
+            .into_iter()
+            .map(LineString::new)
+            .filter(|linestring| linestring.0.len() > 1)
+            .collect(),
+    )
+}
+
+/// Offsets every line and connects them at the corners without caring about self intersections
+fn naive_offset(
+    lines: &[Line<DVec2>],
+    distance: f64,
+    style: BufferStyle,
+) -> Vec<(Line<DVec2>, Source)> {
+    let offset = |line: &Line<DVec2>| line.dir().perp() * distance;
+    let mut raw = vec![];
+    for (i, line) in lines.iter().enumerate() {
+        let o = offset(line);
+        raw.push((Line([line.src() + o, line.dst() + o]), Source::Line(i)));
+
+        let Some(next) = lines.get(i + 1) else {
+            continue;
+        };
+        let v = line.dst();
+        let (a, b) = (v + o, v + offset(next));
+        let (cross, dot) = (line.dir().perp_dot(next.dir()), line.dir().dot(next.dir()));
+
+        let corner = if cross * distance > 0.0 || (cross == 0.0 && dot > 0.0) {
+            // inner corner or no corner at all
+            vec![a, b]
+        } else if cross == 0.0 {
+            // the line turns back onto itself, the corner goes around the front
+            match style.join {
+                JoinStyle::Round => arc(v, a - v, -PI * distance.signum(), style.arc_segments),
+                _ => {
+                    let front = line.dir() * distance.abs();
+                    vec![a, a + front, b + front, b]
+                }
+            }
+        } else {
+            match style.join {
+                JoinStyle::Round => arc(v, a - v, cross.atan2(dot), style.arc_segments),
+                JoinStyle::Miter { limit } => {
+                    let half = (a - v) + (b - v);
+                    // distance of the tip relative to the offset distance
+                    let ratio = 2.0 * distance.abs() / half.length();
+                    if ratio <= limit {
+                        vec![a, v + half.normalize() * distance.abs() * ratio, b]
+                    } else {
+                        vec![a, b]
+                    }
+                }
+                JoinStyle::Bevel => vec![a, b],
+            }
+        };
+        let source = if cross * distance > 0.0 {
+            Source::InnerCorner
+        } else {
+            Source::OuterCorner(i + 1)
+        };
+        raw.extend(
+            corner
+                .windows(2)
+                .filter(|w| w[0] != w[1])
+                .map(|w| (Line([w[0], w[1]]), source)),
+        );
+    }
+    raw
+}
+
+/// Points on the arc around `center`, starting at `center + from` and rotating counter-clockwise
+/// by `angle`
+fn arc(center: DVec2, from: DVec2, angle: f64, arc_segments: usize) -> Vec<DVec2> {
+    let steps = ((angle.abs() / TAU) * arc_segments as f64).ceil().max(1.0) as usize;
+    (0..=steps)
+        .map(|step| center + DVec2::from_angle(angle * step as f64 / steps as f64).rotate(from))
+        .collect()
+}
+
+/// Scalars of the intersection point on both segments, if the segments intersect
+fn segment_intersection([a, b]: [DVec2; 2], [c, d]: [DVec2; 2]) -> Option<(f64, f64)> {
+    let (r, s) = (b - a, d - c);
+    let det = r.perp_dot(s);
+    if det == 0.0 {
+        return None;
+    }
+    let t = (c - a).perp_dot(s) / det;
+    let u = (c - a).perp_dot(r) / det;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then_some((t, u))
+}
+
+fn distance_to_line(line: Line<DVec2>, p: DVec2) -> f64 {
+    line.pos_scaled(line.scalar_of(p).clamp(0.0, 1.0))
+        .distance(p)
+}
+
+#[cfg(test)]
+mod offset_curve_tests {
+    use super::*;
+    use crate::LinesIter;
+
+    fn miter() -> BufferStyle {
+        BufferStyle {
+            join: JoinStyle::Miter { limit: 2.0 },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn straight_line_both_sides() {
+        let line = Line([DVec2::ZERO, DVec2::X * 2.0]);
+
+        let left = line.offset_curve(1.0, miter());
+        assert_eq!(
+            left.0,
+            vec![LineString::new(vec![DVec2::Y, DVec2::new(2.0, 1.0)])]
+        );
+
+        let right = line.offset_curve(-1.0, miter());
+        assert_eq!(
+            right.0,
+            vec![LineString::new(vec![-DVec2::Y, DVec2::new(2.0, -1.0)])]
+        );
+    }
+
+    #[test]
+    fn outer_corner_miter() {
+        let linestring = LineString::new(vec![DVec2::ZERO, DVec2::X * 2.0, DVec2::new(2.0, 2.0)]);
+
+        let right = linestring.offset_curve(-0.5, miter());
+
+        assert_eq!(right.0.len(), 1);
+        let expected = [
+            DVec2::new(0.0, -0.5),
+            DVec2::new(2.5, -0.5),
+            DVec2::new(2.5, 2.0),
+        ];
+        assert_eq!(right.0[0].0.len(), expected.len());
+        right.0[0]
+            .0
+            .iter()
+            .zip(expected)
+            .for_each(|(p, e)| assert!(p.abs_diff_eq(e, 1e-9)));
+    }
+
+    #[test]
+    fn inner_loops_are_removed() {
+        // a zig-zag with segments shorter than the offset distance
+        let linestring = LineString::new(vec![
+            DVec2::ZERO,
+            DVec2::new(4.0, 0.0),
+            DVec2::new(4.2, 0.3),
+            DVec2::new(4.4, 0.0),
+            DVec2::new(8.0, 0.0),
+        ]);
+
+        let below = linestring.offset_curve(-1.0, BufferStyle::default());
+
+        // the chords of the round corners come slightly closer
+        assert!(below.iter_lines().all(|offset_line| {
+            linestring
+                .iter_lines()
+                .all(|line| distance_to_line(line, offset_line.center()) >= 1.0 - 0.01)
+        }));
+        assert_eq!(below.0.len(), 1);
+        // no self intersections remain
+        let offset_lines = below.iter_lines().collect::<Vec<_>>();
+        for (i, a) in offset_lines.iter().enumerate() {
+            for b in offset_lines.iter().skip(i + 2) {
+                assert!(segment_intersection(a.0, b.0).is_none());
+            }
+        }
+
+        let narrow_hairpin = LineString::new(vec![
+            DVec2::ZERO,
+            DVec2::new(4.0, 0.0),
+            DVec2::new(4.0, 0.5),
+            DVec2::new(0.0, 0.5),
+        ]);
+        assert!(narrow_hairpin
+            .offset_curve(1.0, BufferStyle::default())
+            .0
+            .is_empty());
+    }
+
+    #[test]
+    fn offset_in_3d_far_from_the_origin() {
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        let linestring = LineString::new(
+            [DVec3::ZERO, DVec3::X * 2.0, DVec3::new(2.0, 2.0, 0.0)]
+                .map(|p| p + offset)
+                .to_vec(),
+        );
+        let floor = DWorkplane::from_normal_and_origin(DVec3::Z, offset).unwrap();
+
+        let right = linestring.offset_curve_in(floor, -0.5, miter());
+
+        assert_eq!(right.0.len(), 1);
+        let expected = [
+            DVec3::new(0.0, -0.5, 0.0),
+            DVec3::new(2.5, -0.5, 0.0),
+            DVec3::new(2.5, 2.0, 0.0),
+        ];
+        assert_eq!(right.0[0].0.len(), expected.len());
+        right.0[0]
+            .0
+            .iter()
+            .zip(expected)
+            .for_each(|(p, e)| assert!((*p - offset).abs_diff_eq(e, 1e-9), "{p}"));
+    }
+}
@@ -0,0 +1,289 @@
+use bevy_math::{DVec2, Vec2};
+
+use crate::{
+    algorithms::rectangle_partition, errors::GeometryError, primitives::*, BoolOps as _, Map, Point,
+};
+
+/// Partitions of areas for layouts, e.g. of tiles or panels on floors and ceilings.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let l_shape = Ring::new([
+///     Vec2::ZERO,
+///     Vec2::new(3.0, 0.0),
+///     Vec2::new(3.0, 1.0),
+///     Vec2::new(1.0, 1.0),
+///     Vec2::new(1.0, 2.0),
+///     Vec2::new(0.0, 2.0),
+/// ])
+/// .to_polygon();
+///
+/// let rectangles = l_shape.rectangles().unwrap();
+/// assert_eq!(rectangles.len(), 2);
+///
+/// // strips of width 0.5 running along the X axis
+/// let strips = l_shape.strips(Vec2::X, 0.5).unwrap();
+/// assert_eq!(strips.len(), 4);
+/// assert!((strips[0].area() - 1.5).abs() < 0.0001);
+/// assert!((strips[3].area() - 0.5).abs() < 0.0001);
+/// ```
+pub trait Partition {
+    type P: Point;
+
+    /// Splits a rectilinear area, i.e. one with only axis-aligned edges, into the minimal number of
+    /// axis-aligned rectangles. Fails if any edge isn't exactly axis-aligned.
+    fn rectangles(&self) -> Result<MultiRing<Self::P>, GeometryError>;
+
+    /// Cuts the area into strips of the given width which run along `direction`. The first strip
+    /// starts at the right side of the area, seen in `direction`. Every strip is returned, even if
+    /// it's empty because the area has a gap there. Fails if the direction is zero or the width
+    /// isn't positive.
+    fn strips(
+        &self,
+        direction: Self::P,
+        width: f64,
+    ) -> Result<Vec<MultiPolygon<Self::P>>, GeometryError>;
+}
+
+fn strips<T: crate::IterPoints<P = DVec2>>(
+    area: &T,
+    direction: DVec2,
+    width: f64,
+    intersection: impl Fn(&Polygon<DVec2>) -> MultiPolygon<DVec2>,
+) -> Result<Vec<MultiPolygon<DVec2>>, GeometryError> {
+    let direction = direction
+        .try_normalize()
+        .ok_or(GeometryError::InvalidGeometry)?;
+    if !(width.is_finite() && width > 0.0) {
+        return Err(GeometryError::InvalidGeometry);
+    }
+    let normal = direction.perp();
+    let (min, max) =
+        area.iter_points()
+            .fold((DVec2::INFINITY, DVec2::NEG_INFINITY), |(min, max), p| {
+                let p = DVec2::new(p.dot(direction), p.dot(normal));
+                (min.min(p), max.max(p))
+            });
+    if !(min.is_finite() && max.is_finite()) {
+        return Ok(vec![]);
+    }
+
+    let count = ((max.y - min.y) / width).ceil().max(1.0) as usize;
+    let along = [min.x - width, max.x + width];
+    Ok((0..count)
+        .map(|i| {
+            let across = [min.y + i as f64 * width, min.y + (i + 1) as f64 * width];
+            let band = Ring::new(
+                [
+                    (along[0], across[0]),
+                    (along[1], across[0]),
+                    (along[1], across[1]),
+                    (along[0], across[1]),
+                ]
+                .map(|(x, y)| direction * x + normal * y),
+            );
+            intersection(&band.to_polygon())
+        })
+        .collect())
+}
+
+impl Partition for Polygon<DVec2> {
+    type P = DVec2;
+
+    fn rectangles(&self) -> Result<MultiRing<DVec2>, GeometryError> {
+        rectangle_partition(self).map(MultiRing)
+    }
+
+    fn strips(
+        &self,
+        direction: DVec2,
+        width: f64,
+    ) -> Result<Vec<MultiPolygon<DVec2>>, GeometryError> {
+        strips(self, direction, width, |band| band.intersection(self))
+    }
+}
+
+impl Partition for MultiPolygon<DVec2> {
+    type P = DVec2;
+
+    fn rectangles(&self) -> Result<MultiRing<DVec2>, GeometryError> {
+        let mut rectangles = vec![];
+        for polygon in self.iter() {
+            rectangles.extend(rectangle_partition(polygon)?);
+        }
+        Ok(MultiRing(rectangles))
+    }
+
+    fn strips(
+        &self,
+        direction: DVec2,
+        width: f64,
+    ) -> Result<Vec<MultiPolygon<DVec2>>, GeometryError> {
+        strips(self, direction, width, |band| band.intersection(self))
+    }
+}
+
+macro_rules! impl_partition_f32 {
+    ($($typename:ident),*) => {
+        $(
+            impl Partition for $typename<Vec2> {
+                type P = Vec2;
+
+                fn rectangles(&self) -> Result<MultiRing<Vec2>, GeometryError> {
+                    self.map(|p| p.as_dvec2())
+                        .rectangles()
+                        .map(|rectangles| rectangles.map(|p| p.as_vec2()))
+                }
+
+                fn strips(
+                    &self,
+                    direction: Vec2,
+                    width: f64,
+                ) -> Result<Vec<MultiPolygon<Vec2>>, GeometryError> {
+                    self.map(|p| p.as_dvec2())
+                        .strips(direction.as_dvec2(), width)
+                        .map(|strips| {
+                            strips
+                                .into_iter()
+                                .map(|strip| strip.map(|p| p.as_vec2()))
+                                .collect()
+                        })
+                }
+            }
+        )*
+    };
+}
+
+impl_partition_f32!(Polygon, MultiPolygon);
+
+#[cfg(test)]
+mod partition_tests {
+    use super::*;
+    use crate::Area;
+
+    fn ring(points: &[(f32, f32)]) -> Ring<Vec2> {
+        Ring::new(
+            points
+                .iter()
+                .map(|(x, y)| Vec2::new(*x, *y))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn is_rectangle(ring: &Ring<Vec2>) -> bool {
+        let points = ring.points_open();
+        points.len() == 4
+            && points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .all(|(a, b)| a.x == b.x || a.y == b.y)
+    }
+
+    #[test]
+    fn chords_between_reflex_vertices_are_preferred() {
+        // a cross, the two horizontal chords give three rectangles instead of five
+        let cross = ring(&[
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (3.0, 1.0),
+            (3.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 3.0),
+            (1.0, 3.0),
+            (1.0, 2.0),
+            (0.0, 2.0),
+            (0.0, 1.0),
+            (1.0, 1.0),
+        ])
+        .to_polygon();
+
+        let rectangles = cross.rectangles().unwrap();
+
+        assert_eq!(rectangles.len(), 3);
+        assert!(rectangles.iter().all(is_rectangle));
+        let area = rectangles.iter().map(|r| r.area()).sum::<f32>();
+        assert_eq!(area, 5.0);
+    }
+
+    #[test]
+    fn polygon_with_hole() {
+        let polygon = Polygon::new(
+            ring(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]),
+            MultiRing(vec![ring(&[
+                (1.0, 1.0),
+                (1.0, 2.0),
+                (2.0, 2.0),
+                (2.0, 1.0),
+            ])]),
+        );
+
+        let rectangles = polygon.rectangles().unwrap();
+
+        // four reflex vertices without any chords between them
+        assert_eq!(rectangles.len(), 4);
+        assert!(rectangles.iter().all(is_rectangle));
+        let area = rectangles.iter().map(|r| r.area()).sum::<f32>();
+        assert_eq!(area, 15.0);
+    }
+
+    #[test]
+    fn squares_touching_in_a_corner() {
+        // the boundary passes the shared corner twice, the faces there have to be traced with
+        // the sharpest left turn to keep the squares apart
+        let squares = ring(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (1.0, 1.0),
+            (2.0, 1.0),
+            (2.0, 2.0),
+            (1.0, 2.0),
+            (1.0, 1.0),
+            (0.0, 1.0),
+        ])
+        .to_polygon();
+
+        let rectangles = squares.rectangles().unwrap();
+
+        assert_eq!(rectangles.len(), 2);
+        assert!(rectangles.iter().all(is_rectangle));
+        assert!(rectangles.iter().all(|r| r.area() == 1.0));
+    }
+
+    #[test]
+    fn staircase_and_non_rectilinear_input() {
+        let stairs = ring(&[
+            (0.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 1.0),
+            (3.0, 1.0),
+            (3.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 3.0),
+            (1.0, 3.0),
+            (1.0, 4.0),
+            (0.0, 4.0),
+        ])
+        .to_polygon();
+
+        assert_eq!(stairs.rectangles().unwrap().len(), 4);
+        let triangle = ring(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]).to_polygon();
+        assert!(triangle.rectangles().is_err());
+    }
+
+    #[test]
+    fn diagonal_strips() {
+        let square = ring(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]).to_polygon();
+
+        let strips = square.strips(Vec2::ONE, 0.25).unwrap();
+
+        // the diagonal of the square is sqrt(2) long
+        assert_eq!(strips.len(), 6);
+        let area = strips.iter().map(|strip| strip.area()).sum::<f32>();
+        assert!((area - 1.0).abs() < 0.0001);
+        assert!(square.strips(Vec2::ZERO, 0.25).is_err());
+        assert!(square.strips(Vec2::X, 0.0).is_err());
+    }
+}
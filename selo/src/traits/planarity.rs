@@ -0,0 +1,132 @@
+use crate::{
+    errors::GeometryError,
+    prelude::{EmbeddingPlane, PlaneFit, WorkplanePoint},
+    IterPoints, Point,
+};
+
+/// Checks whether 3D geometry is flat enough to be embedded into a plane, based on the least
+/// squares plane through its points.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// // a scanned face with a bit of noise
+/// let face = Ring::new([
+///     Vec3::new(0.0, 0.0, 0.002),
+///     Vec3::new(4.0, 0.0, 0.0),
+///     Vec3::new(4.0, 3.0, -0.001),
+///     Vec3::new(0.0, 3.0, 0.0),
+/// ]);
+///
+/// let plane = face.planarity(0.01).unwrap();
+/// let flat = face.embed(plane);
+///
+/// assert!((flat.area() - 12.0).abs() < 0.001);
+/// assert!(matches!(face.planarity(0.0001), Err(GeometryError::NotCoplanar)));
+/// ```
+pub trait Planarity {
+    type Workplane: EmbeddingPlane;
+
+    /// The least squares plane through all points along with their deviations from it, see
+    /// [`Workplane::best_fit`](crate::prelude::Workplane::best_fit)
+    fn best_fit_plane(&self) -> Result<PlaneFit<Self::Workplane>, GeometryError>;
+
+    /// The least squares plane through all points, if none of them is farther than `tolerance`
+    /// away from it. Fails with [`GeometryError::NotCoplanar`] otherwise and with
+    /// [`GeometryError::InvalidGeometry`] if there's no plane through the points at all.
+    fn planarity(
+        &self,
+        tolerance: <<Self::Workplane as EmbeddingPlane>::P as Point>::S,
+    ) -> Result<Self::Workplane, GeometryError>;
+}
+
+impl<T: IterPoints> Planarity for T
+where
+    T::P: WorkplanePoint,
+{
+    type Workplane = <T::P as WorkplanePoint>::Workplane;
+
+    fn best_fit_plane(&self) -> Result<PlaneFit<Self::Workplane>, GeometryError> {
+        Self::Workplane::best_fit(self)
+    }
+
+    fn planarity(&self, tolerance: <T::P as Point>::S) -> Result<Self::Workplane, GeometryError> {
+        let fit = self.best_fit_plane()?;
+        if fit.max_deviation <= tolerance {
+            Ok(fit.workplane)
+        } else {
+            Err(GeometryError::NotCoplanar)
+        }
+    }
+}
+
+#[cfg(test)]
+mod planarity_tests {
+    use bevy_math::{DVec3, Dir3, Vec3};
+
+    use super::*;
+    use crate::{LineString, Ring};
+
+    #[test]
+    fn noisy_points_far_from_the_origin() {
+        // a tilted square with deterministic noise of at most 1mm, a few hundred kilometres away
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        let (u, v) = (DVec3::new(1.0, 0.0, 0.5), DVec3::new(0.0, 1.0, 0.0));
+        let normal = u.cross(v).normalize();
+        let points = (0..10)
+            .flat_map(|i| (0..10).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                let noise = ((i * 7 + j * 3) % 5) as f64 - 2.0;
+                offset + u * i as f64 + v * j as f64 + normal * noise * 0.0005
+            })
+            .collect::<Vec<_>>();
+        let cloud = LineString::new(points);
+
+        let fit = cloud.best_fit_plane().unwrap();
+
+        assert!(fit.workplane.normal().dot(normal).abs() > 1.0 - 1e-9);
+        assert!(fit.max_deviation <= 0.001 + 1e-9);
+        assert!(fit.rms_deviation > 0.0 && fit.rms_deviation < fit.max_deviation);
+        assert!(cloud.planarity(0.002).is_ok());
+        assert!(cloud.planarity(0.0005).is_err());
+    }
+
+    #[test]
+    fn degenerate_points() {
+        let collinear = LineString::new(vec![Vec3::ZERO, Vec3::X, Vec3::X * 2.0]);
+        let two = LineString::new(vec![Vec3::ZERO, Vec3::X]);
+        assert!(matches!(
+            collinear.best_fit_plane(),
+            Err(GeometryError::InvalidGeometry)
+        ));
+        assert!(two.planarity(1.0).is_err());
+
+        // the normal follows the winding of rings
+        let clockwise = Ring::new([Vec3::ZERO, Vec3::Y, Vec3::ONE.with_z(0.0), Vec3::X]);
+        assert_eq!(clockwise.planarity(0.0).unwrap().normal(), Dir3::NEG_Z);
+    }
+
+    #[test]
+    fn closing_point_weighs_once() {
+        let points = vec![
+            DVec3::new(0.0, 0.0, 0.01),
+            DVec3::new(4.0, 0.0, 0.0),
+            DVec3::new(4.0, 3.0, -0.02),
+            DVec3::new(1.0, 2.0, 0.0),
+            DVec3::new(0.0, 3.0, 0.01),
+        ];
+        let open = LineString::new(points.clone()).best_fit_plane().unwrap();
+        let closed = LineString::new([points.clone(), vec![points[0]]].concat())
+            .best_fit_plane()
+            .unwrap();
+        // the ring repeats its first point when iterating over its points
+        let ring = Ring::new(points).best_fit_plane().unwrap();
+
+        for fit in [closed, ring] {
+            assert!(fit.workplane.abs_diff_eq(&open.workplane, 1e-12));
+            assert_eq!(fit.max_deviation, open.max_deviation);
+            assert_eq!(fit.rms_deviation, open.rms_deviation);
+        }
+    }
+}
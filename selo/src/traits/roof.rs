@@ -0,0 +1,316 @@
+use bevy_math::{Vec2, Vec3};
+
+use crate::{
+    algorithms::{weighted_straight_skeleton, SkeletonGraph},
+    errors::GeometryError,
+    primitives::*,
+    Area, Map, Orient2d, Triangulate, TriangulationConfig,
+};
+
+/// How the roof rises from an edge of the footprint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoofSlope {
+    /// A roof face rising with the given angle in radians, angles of 90° or more are gables
+    Pitch(f32),
+    /// A vertical wall, the neighbouring roof faces meet above the edge
+    Gable,
+}
+
+/// A roof generated by [`RoofGeometry`]
+#[derive(Debug, Clone, Default)]
+pub struct Roof {
+    /// One face for every pitched edge of the footprint
+    pub faces: Vec<Polygon<Vec3>>,
+    /// The vertical walls above the gable edges of the footprint
+    pub gables: Vec<Polygon<Vec3>>,
+    /// The triangulated `faces`, all triangles face upwards
+    pub mesh: MultiTriangle<Vec3>,
+}
+
+/// Generates roofs on top of a footprint from its weighted [straight
+/// skeleton](super::StraightSkeleton).
+///
+/// The footprint lies in the XY plane and the roof rises along the Z axis. Every edge gets its own
+/// [`RoofSlope`], the edges are counted ring by ring in the order of the input like
+/// [`LinesIter::iter_lines`](crate::LinesIter::iter_lines), regardless of the orientation of the
+/// rings.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let footprint = Ring::new([Vec2::ZERO, Vec2::X * 4.0, Vec2::new(4.0, 2.0), Vec2::Y * 2.0]);
+///
+/// // a gable roof with the gables on the short sides
+/// let pitch = RoofSlope::Pitch(45f32.to_radians());
+/// let slopes = [pitch, RoofSlope::Gable, pitch, RoofSlope::Gable];
+/// let roof = footprint.to_polygon().roof(&slopes).unwrap();
+///
+/// assert_eq!(roof.faces.len(), 2);
+/// assert_eq!(roof.gables.len(), 2);
+/// // the ridge is one unit above the footprint
+/// let height = roof.mesh.0.iter().flat_map(|t| t.0).map(|p| p.z).fold(0.0, f32::max);
+/// assert!((height - 1.0).abs() < 0.0001);
+/// ```
+pub trait RoofGeometry {
+    /// Generates a roof with one slope per edge of the footprint. Fails if the number of slopes
+    /// doesn't match the number of edges or if a pitch isn't positive.
+    fn roof(&self, slopes: &[RoofSlope]) -> Result<Roof, GeometryError>;
+
+    /// Generates a hip roof where all faces rise with the same angle in radians
+    fn hip_roof(&self, pitch: f32) -> Result<Roof, GeometryError>;
+}
+
+impl RoofGeometry for Polygon<Vec2> {
+    fn roof(&self, slopes: &[RoofSlope]) -> Result<Roof, GeometryError> {
+        // the edges only move with the speed given by their slope, this way the time at which the
+        // wavefront reaches a node is its height
+        let weights = slopes
+            .iter()
+            .map(|slope| match slope {
+                RoofSlope::Pitch(angle) if !angle.is_finite() || *angle <= 0.0 => {
+                    Err(GeometryError::InvalidGeometry)
+                }
+                RoofSlope::Pitch(angle) if *angle < std::f32::consts::FRAC_PI_2 => {
+                    Ok(1.0 / (*angle as f64).tan())
+                }
+                _ => Ok(0.0),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if weights.len() != edge_count(self) {
+            return Err(GeometryError::InvalidGeometry);
+        }
+
+        // the skeleton counts the edges after orienting the rings. Flipping a ring reverses its
+        // points, so its edge `i` runs backwards along the edge `n - 2 - i` of the input
+        let oriented = self.orient_default();
+        let mut offset = 0;
+        let mut oriented_weights = Vec::with_capacity(weights.len());
+        for (ring, oriented_ring) in self.iter_rings().zip(oriented.iter_rings()) {
+            let n = ring.points_open().len();
+            if n < 3 {
+                continue;
+            }
+            let own = &weights[offset..offset + n];
+            if ring.points_open() == oriented_ring.points_open() {
+                oriented_weights.extend(own);
+            } else {
+                oriented_weights.extend((0..n).map(|i| own[(2 * n - 2 - i) % n]));
+            }
+            offset += n;
+        }
+
+        let skeleton = weighted_straight_skeleton(&oriented.map(|p| p.as_dvec2()), |edge| {
+            oriented_weights[edge]
+        })?
+        .map(|p| p.as_vec2(), |t| t as f32);
+        roof_from_skeleton(&skeleton, &oriented_weights)
+    }
+
+    fn hip_roof(&self, pitch: f32) -> Result<Roof, GeometryError> {
+        self.roof(&vec![RoofSlope::Pitch(pitch); edge_count(self)])
+    }
+}
+
+impl RoofGeometry for MultiPolygon<Vec2> {
+    fn roof(&self, slopes: &[RoofSlope]) -> Result<Roof, GeometryError> {
+        let mut slopes = slopes;
+        let mut roof = Roof::default();
+        for polygon in self.iter() {
+            let count = edge_count(polygon);
+            if slopes.len() < count {
+                return Err(GeometryError::InvalidGeometry);
+            }
+            let (own, rest) = slopes.split_at(count);
+            let part = polygon.roof(own)?;
+            roof.faces.extend(part.faces);
+            roof.gables.extend(part.gables);
+            roof.mesh.0.extend(part.mesh.0);
+            slopes = rest;
+        }
+        if !slopes.is_empty() {
+            return Err(GeometryError::InvalidGeometry);
+        }
+        Ok(roof)
+    }
+
+    fn hip_roof(&self, pitch: f32) -> Result<Roof, GeometryError> {
+        let count = self.iter().map(edge_count).sum();
+        self.roof(&vec![RoofSlope::Pitch(pitch); count])
+    }
+}
+
+/// The number of edges which take part in the straight skeleton
+fn edge_count(polygon: &Polygon<Vec2>) -> usize {
+    polygon
+        .iter_rings()
+        .map(|ring| ring.points_open().len())
+        .filter(|len| *len >= 3)
+        .sum()
+}
+
+fn roof_from_skeleton(
+    skeleton: &SkeletonGraph<Vec2>,
+    weights: &[f64],
+) -> Result<Roof, GeometryError> {
+    let lift = |node: &usize| {
+        let node = skeleton.nodes[*node];
+        node.position.extend(node.time)
+    };
+
+    let mut roof = Roof::default();
+    for (face, weight) in skeleton.faces.iter().zip(weights) {
+        let polygon = Ring::new(face.nodes.iter().map(lift).collect::<Vec<_>>()).to_polygon();
+        if polygon.exterior().points_open().len() < 3 {
+            continue;
+        }
+        if *weight == 0.0 {
+            roof.gables.push(polygon);
+            continue;
+        }
+
+        // the height of a point of the face only depends on its distance to the edge
+        let edge = face.edge;
+        let normal = edge.dir().perp();
+        let rise = 1.0 / *weight as f32;
+        let height = |p: Vec2| normal.dot(p - edge.src()) * rise;
+
+        let footprint = Ring::new(
+            face.nodes
+                .iter()
+                .map(|node| skeleton.nodes[*node].position)
+                .collect::<Vec<_>>(),
+        );
+        if footprint.area().abs() > f32::EPSILON {
+            roof.mesh.0.extend(
+                footprint
+                    .to_polygon()
+                    .triangulate(TriangulationConfig::default())?
+                    .0
+                    .into_iter()
+                    .map(|triangle| {
+                        let [a, b, c] = triangle.0;
+                        let upwards = if (b - a).perp_dot(c - a) >= 0.0 {
+                            [a, b, c]
+                        } else {
+                            [a, c, b]
+                        };
+                        Triangle(upwards.map(|p| p.extend(height(p))))
+                    }),
+            );
+        }
+        roof.faces.push(polygon);
+    }
+    Ok(roof)
+}
+
+#[cfg(test)]
+mod roof_tests {
+    use super::*;
+
+    fn mesh_area(roof: &Roof) -> f32 {
+        roof.mesh
+            .0
+            .iter()
+            .map(|triangle| triangle.area().length())
+            .sum()
+    }
+
+    #[test]
+    fn hip_roof_on_square() {
+        let square = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p * 2.0));
+
+        let roof = square.to_polygon().hip_roof(45f32.to_radians()).unwrap();
+
+        assert_eq!(roof.faces.len(), 4);
+        assert!(roof.gables.is_empty());
+        assert!(roof.faces.iter().all(|face| face
+            .exterior()
+            .points_open()
+            .iter()
+            .any(|p| p.abs_diff_eq(Vec3::new(1.0, 1.0, 1.0), 0.0001))));
+        assert!((mesh_area(&roof) - 4.0 * 2f32.sqrt()).abs() < 0.001);
+        assert!(roof.mesh.0.iter().all(|triangle| triangle.area().z > 0.0));
+    }
+
+    #[test]
+    fn gable_roof_with_different_pitches() {
+        let footprint = Ring::new([
+            Vec2::ZERO,
+            Vec2::X * 4.0,
+            Vec2::new(4.0, 3.0),
+            Vec2::Y * 3.0,
+        ]);
+        // steep at the front, shallow at the back
+        let slopes = [
+            RoofSlope::Pitch(60f32.to_radians()),
+            RoofSlope::Gable,
+            RoofSlope::Pitch(30f32.to_radians()),
+            RoofSlope::Gable,
+        ];
+
+        let roof = footprint.to_polygon().roof(&slopes).unwrap();
+
+        // the faces meet where 3^0.5 * y = 3^-0.5 * (3 - y)
+        let ridge = roof.gables[0]
+            .exterior()
+            .points_open()
+            .iter()
+            .copied()
+            .max_by(|a, b| a.z.total_cmp(&b.z))
+            .unwrap();
+        assert!(ridge.abs_diff_eq(Vec3::new(4.0, 0.75, 0.75 * 3f32.sqrt()), 0.0001));
+        let expected = 4.0 * (0.75 / 60f32.to_radians().cos() + 2.25 / 30f32.to_radians().cos());
+        assert!((mesh_area(&roof) - expected).abs() < 0.001);
+    }
+
+    #[test]
+    fn invalid_slopes() {
+        let square = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]).to_polygon();
+        let pitch = RoofSlope::Pitch(0.5);
+
+        assert!(square.roof(&[pitch; 3]).is_err());
+        assert!(square
+            .roof(&[pitch, pitch, pitch, RoofSlope::Pitch(0.0)])
+            .is_err());
+    }
+
+    #[test]
+    fn slopes_follow_clockwise_input() {
+        // the footprint of `gable_roof_with_different_pitches` in clockwise order
+        let footprint = Ring::new([
+            Vec2::ZERO,
+            Vec2::Y * 3.0,
+            Vec2::new(4.0, 3.0),
+            Vec2::X * 4.0,
+        ]);
+        let slopes = [
+            RoofSlope::Gable,
+            RoofSlope::Pitch(30f32.to_radians()),
+            RoofSlope::Gable,
+            RoofSlope::Pitch(60f32.to_radians()),
+        ];
+
+        let roof = footprint.to_polygon().roof(&slopes).unwrap();
+
+        assert_eq!(roof.gables.len(), 2);
+        // both gables are on the short sides, with the ridge closer to the steep front
+        for gable in &roof.gables {
+            let points = gable.exterior().points_open();
+            assert!(
+                points.iter().all(|p| p.x.abs() < 0.0001)
+                    || points.iter().all(|p| (p.x - 4.0).abs() < 0.0001),
+                "{points:?}"
+            );
+            let ridge = points
+                .iter()
+                .copied()
+                .max_by(|a, b| a.z.total_cmp(&b.z))
+                .unwrap();
+            assert!((ridge.y - 0.75).abs() < 0.0001);
+            assert!((ridge.z - 0.75 * 3f32.sqrt()).abs() < 0.0001);
+        }
+        let expected = 4.0 * (0.75 / 60f32.to_radians().cos() + 2.25 / 30f32.to_radians().cos());
+        assert!((mesh_area(&roof) - expected).abs() < 0.001);
+    }
+}
@@ -0,0 +1,347 @@
+use bevy_math::{DVec2, DVec3, Vec2, Vec3};
+
+use crate::{
+    algorithms::section,
+    errors::GeometryError,
+    prelude::{DWorkplane, EmbeddingPlane, Workplane},
+    LineString, Map, MultiLineString, MultiPolygon, MultiRing, MultiTriangle, Normal, Point,
+    Point2, Polygon, Ring, Triangle,
+};
+
+/// The intersection of 3D geometry with a plane, in the 2D coordinates of the plane as used by
+/// [`Embed`](crate::Embed).
+#[derive(Debug, Clone)]
+pub struct Section<P: Point2> {
+    /// Closed contours. For closed surfaces with outward normals they run counter-clockwise around
+    /// the material.
+    pub rings: MultiRing<P>,
+    /// Open contours, e.g. where the surface has a boundary
+    pub lines: MultiLineString<P>,
+}
+
+impl<P: Point2> Section<P> {
+    /// Whether the plane doesn't intersect the geometry at all
+    pub fn is_empty(&self) -> bool {
+        self.rings.0.is_empty() && self.lines.0.is_empty()
+    }
+}
+
+/// Slices 3D surfaces by planes, e.g. for floor plan sections or contour lines of terrain.
+///
+/// Vertices exactly on the plane count as above it, so faces lying in the plane don't show up in
+/// the section.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// // a tetrahedron with the tip at the top
+/// let [a, b, c, d] = [Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::Z];
+/// let tetrahedron = MultiTriangle(vec![
+///     Triangle([a, c, b]),
+///     Triangle([a, b, d]),
+///     Triangle([b, c, d]),
+///     Triangle([c, a, d]),
+/// ]);
+/// let floor = Workplane::from_normal_and_origin(Dir3::Z, Vec3::Z * 0.5);
+///
+/// let section = tetrahedron.slice(&floor);
+///
+/// assert_eq!(section.rings.len(), 1);
+/// assert!(section.lines.0.is_empty());
+/// assert!((section.rings[0].area() - 0.125).abs() < 0.0001);
+///
+/// let contours = tetrahedron.slice_stack(&floor, 0.25).unwrap();
+/// let heights = contours.iter().map(|(height, _)| *height).collect::<Vec<_>>();
+/// // the lowest plane touches the bottom which counts as above it
+/// assert_eq!(heights, [-0.5, -0.25, 0.0, 0.25, 0.5]);
+/// assert!(contours[0].1.is_empty() && !contours[1].1.is_empty());
+/// ```
+pub trait SliceByPlane {
+    type P: Point2;
+    /// [`Workplane`] for `f32` geometry and [`DWorkplane`] for `f64` geometry
+    type Workplane: EmbeddingPlane;
+
+    /// The section of the geometry with the plane
+    fn slice(&self, plane: &Self::Workplane) -> Section<Self::P>;
+
+    /// The sections with a stack of planes parallel to `plane` at multiples of `spacing` from it,
+    /// along with their signed offset. Only the planes between the lowest and highest point of the
+    /// geometry are included. Fails if `spacing` isn't positive.
+    #[allow(clippy::type_complexity)]
+    fn slice_stack(
+        &self,
+        plane: &Self::Workplane,
+        spacing: <Self::P as Point>::S,
+    ) -> Result<Vec<(<Self::P as Point>::S, Section<Self::P>)>, GeometryError>;
+}
+
+/// The faces of a surface together with their normals
+trait Faces {
+    fn faces(&self) -> Vec<(DVec3, Vec<&[DVec3]>)>;
+}
+
+impl Faces for Triangle<DVec3> {
+    fn faces(&self) -> Vec<(DVec3, Vec<&[DVec3]>)> {
+        vec![(self.normal(), vec![&self.0[..]])]
+    }
+}
+
+impl Faces for MultiTriangle<DVec3> {
+    fn faces(&self) -> Vec<(DVec3, Vec<&[DVec3]>)> {
+        self.0.iter().flat_map(|t| t.faces()).collect()
+    }
+}
+
+impl Faces for Polygon<DVec3> {
+    fn faces(&self) -> Vec<(DVec3, Vec<&[DVec3]>)> {
+        vec![(
+            self.normal(),
+            self.iter_rings().map(|ring| ring.points_open()).collect(),
+        )]
+    }
+}
+
+impl Faces for MultiPolygon<DVec3> {
+    fn faces(&self) -> Vec<(DVec3, Vec<&[DVec3]>)> {
+        self.iter().flat_map(|polygon| polygon.faces()).collect()
+    }
+}
+
+fn slice<T: Faces>(geometry: &T, plane: &DWorkplane) -> Section<DVec2> {
+    let (rings, lines) = section(
+        geometry.faces(),
+        plane.normal(),
+        plane.origin(),
+        plane.xy_projection(),
+    );
+    Section {
+        rings: MultiRing(rings.into_iter().map(Ring::new).collect()),
+        lines: MultiLineString(lines.into_iter().map(LineString::new).collect()),
+    }
+}
+
+fn slice_stack<T: Faces>(
+    geometry: &T,
+    plane: &DWorkplane,
+    spacing: f64,
+) -> Result<Vec<(f64, Section<DVec2>)>, GeometryError> {
+    if !(spacing.is_finite() && spacing > 0.0) {
+        return Err(GeometryError::InvalidGeometry);
+    }
+    let faces = geometry.faces();
+    let (min, max) = faces
+        .iter()
+        .flat_map(|(_, rings)| rings.iter().copied().flatten())
+        .map(|p| plane.normal().dot(*p - plane.origin()))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), d| {
+            (min.min(d), max.max(d))
+        });
+    if !(min.is_finite() && max.is_finite()) {
+        return Ok(vec![]);
+    }
+
+    ((min / spacing).ceil() as i64..=(max / spacing).floor() as i64)
+        .map(|i| {
+            let offset = i as f64 * spacing;
+            let plane = DWorkplane::from_normal_and_origin(
+                plane.normal(),
+                plane.origin() + plane.normal() * offset,
+            )?
+            .with_x_axis(plane.x_axis())?;
+            Ok((offset, slice(geometry, &plane)))
+        })
+        .collect()
+}
+
+macro_rules! impl_slice_by_plane {
+    ($($typename:ident),*) => {
+        $(
+            // f32 geometry is sliced in f64 precision
+            impl SliceByPlane for $typename<Vec3> {
+                type P = Vec2;
+                type Workplane = Workplane;
+
+                fn slice(&self, plane: &Workplane) -> Section<Vec2> {
+                    to_f32(slice(&self.map(|p| p.as_dvec3()), &(*plane).into()))
+                }
+
+                fn slice_stack(
+                    &self,
+                    plane: &Workplane,
+                    spacing: f32,
+                ) -> Result<Vec<(f32, Section<Vec2>)>, GeometryError> {
+                    slice_stack(&self.map(|p| p.as_dvec3()), &(*plane).into(), spacing as f64).map(
+                        |sections| {
+                            sections
+                                .into_iter()
+                                .map(|(offset, section)| (offset as f32, to_f32(section)))
+                                .collect()
+                        },
+                    )
+                }
+            }
+
+            impl SliceByPlane for $typename<DVec3> {
+                type P = DVec2;
+                type Workplane = DWorkplane;
+
+                fn slice(&self, plane: &DWorkplane) -> Section<DVec2> {
+                    slice(self, plane)
+                }
+
+                fn slice_stack(
+                    &self,
+                    plane: &DWorkplane,
+                    spacing: f64,
+                ) -> Result<Vec<(f64, Section<DVec2>)>, GeometryError> {
+                    slice_stack(self, plane, spacing)
+                }
+            }
+        )*
+    };
+}
+
+impl_slice_by_plane!(Triangle, MultiTriangle, Polygon, MultiPolygon);
+
+fn to_f32(section: Section<DVec2>) -> Section<Vec2> {
+    Section {
+        rings: section.rings.map(|p| p.as_vec2()),
+        lines: section.lines.map(|p| p.as_vec2()),
+    }
+}
+
+#[cfg(test)]
+mod section_tests {
+    use bevy_math::Dir3;
+
+    use super::*;
+    use crate::{Area, Embed};
+
+    fn cube() -> MultiTriangle<Vec3> {
+        // the faces of the unit cube with outward normals
+        let quad = |a: Vec3, b: Vec3, c: Vec3, d: Vec3| [Triangle([a, b, c]), Triangle([a, c, d])];
+        let p = |x: f32, y: f32, z: f32| Vec3::new(x, y, z);
+        MultiTriangle(
+            [
+                quad(p(0., 0., 0.), p(0., 1., 0.), p(1., 1., 0.), p(1., 0., 0.)),
+                quad(p(0., 0., 1.), p(1., 0., 1.), p(1., 1., 1.), p(0., 1., 1.)),
+                quad(p(0., 0., 0.), p(1., 0., 0.), p(1., 0., 1.), p(0., 0., 1.)),
+                quad(p(0., 1., 0.), p(0., 1., 1.), p(1., 1., 1.), p(1., 1., 0.)),
+                quad(p(0., 0., 0.), p(0., 0., 1.), p(0., 1., 1.), p(0., 1., 0.)),
+                quad(p(1., 0., 0.), p(1., 1., 0.), p(1., 1., 1.), p(1., 0., 1.)),
+            ]
+            .concat(),
+        )
+    }
+
+    #[test]
+    fn cube_sections_are_counter_clockwise() {
+        let cube = cube();
+
+        for normal in [Dir3::Z, Dir3::NEG_X, Dir3::new(Vec3::ONE).unwrap()] {
+            let plane = Workplane::from_normal_and_origin(normal, Vec3::splat(0.5));
+            let section = cube.slice(&plane);
+
+            assert_eq!(section.rings.len(), 1, "{normal:?}");
+            assert!(section.lines.0.is_empty());
+            assert!(section.rings[0].area() > 0.0);
+        }
+
+        let plane = Workplane::from_normal_and_origin(Dir3::Z, Vec3::splat(0.5));
+        let section = cube.slice(&plane);
+        assert!((section.rings[0].area() - 1.0).abs() < 0.0001);
+        // the coordinates are the ones of the embedding into the plane, the diagonals of the
+        // side faces add points in the middle of the edges
+        let expected = Ring::new([Vec3::ZERO, Vec3::X, Vec3::ONE, Vec3::Y]).embed(plane);
+        assert_eq!(section.rings[0].points_open().len(), 8);
+        for p in section.rings[0].points_open() {
+            let on_edge = expected
+                .lines()
+                .any(|line| (line.src() - *p).perp_dot(line.dst() - *p).abs() < 0.0001);
+            assert!(on_edge, "{p:?}");
+        }
+    }
+
+    #[test]
+    fn faces_in_the_plane_are_ignored() {
+        // the top face counts as above the plane, the side faces are cut
+        let top = Workplane::from_normal_and_origin(Dir3::Z, Vec3::Z);
+        let section = cube().slice(&top);
+        assert_eq!(section.rings.len(), 1);
+        assert!(section.lines.0.is_empty());
+
+        // the whole cube counts as above the plane of the bottom face
+        let bottom = Workplane::from_normal_and_origin(Dir3::Z, Vec3::ZERO);
+        assert!(cube().slice(&bottom).is_empty());
+
+        let above = Workplane::from_normal_and_origin(Dir3::Z, Vec3::Z * 2.0);
+        assert!(cube().slice(&above).is_empty());
+    }
+
+    #[test]
+    fn open_surfaces_give_lines() {
+        // a wall made of two polygons standing on the XY plane
+        let wall = MultiPolygon(vec![
+            Ring::new([
+                Vec3::ZERO,
+                Vec3::new(2.0, 0.0, 0.0),
+                Vec3::new(2.0, 0.0, 3.0),
+                Vec3::new(0.0, 0.0, 3.0),
+            ])
+            .to_polygon(),
+            Ring::new([
+                Vec3::new(2.0, 0.0, 0.0),
+                Vec3::new(2.0, 2.0, 0.0),
+                Vec3::new(2.0, 2.0, 3.0),
+                Vec3::new(2.0, 0.0, 3.0),
+            ])
+            .to_polygon(),
+        ])
+        .map(|p| p.as_dvec3());
+        let plane = DWorkplane::from_normal_and_origin(DVec3::Z, DVec3::Z).unwrap();
+
+        let section = wall.slice(&plane);
+
+        assert!(section.rings.0.is_empty());
+        assert_eq!(section.lines.0.len(), 1);
+        assert_eq!(section.lines.0[0].0.len(), 3);
+
+        let stack = wall.slice_stack(&plane, 1.0).unwrap();
+        assert_eq!(stack.len(), 4);
+        let lines = stack
+            .iter()
+            .map(|(_, section)| section.lines.0.len())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [0, 1, 1, 1]);
+        assert!(wall.slice_stack(&plane, 0.0).is_err());
+    }
+
+    #[test]
+    fn cube_far_from_the_origin() {
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        let cube = cube().map(|p| p.as_dvec3() + offset);
+        let plane = DWorkplane::from_normal_and_origin(DVec3::Z, offset + DVec3::splat(0.5))
+            .unwrap()
+            .with_x_axis(DVec3::X)
+            .unwrap();
+
+        let section = cube.slice(&plane);
+
+        assert_eq!(section.rings.len(), 1);
+        assert!((section.rings[0].area() - 1.0).abs() < 1e-9);
+        let injection = plane.xy_injection();
+        for p in section.rings[0].points_open() {
+            let local = injection.transform_point3(p.extend(0.0)) - offset;
+            assert!((local.z - 0.5).abs() < 1e-9, "{local}");
+            let on_edge = [local.x, local.y]
+                .iter()
+                .any(|c| c.abs() < 1e-9 || (c - 1.0).abs() < 1e-9);
+            assert!(on_edge, "{local}");
+        }
+
+        let stack = cube.slice_stack(&plane, 0.25).unwrap();
+        let offsets = stack.iter().map(|(offset, _)| *offset).collect::<Vec<_>>();
+        assert_eq!(offsets, [-0.5, -0.25, 0.0, 0.25, 0.5]);
+    }
+}
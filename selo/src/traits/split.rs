@@ -0,0 +1,438 @@
+use bevy_math::{DVec2, DVec3, Vec2, Vec3};
+use i_overlay::float::slice::FloatSlice as _;
+
+use crate::{
+    errors::GeometryError,
+    prelude::{DWorkplane, Workplane, WorkplanePoint},
+    Embed, IterPoints, Line, LineString, Map, MultiPolygon, Point2, Polygon,
+};
+
+use super::{paths_to_poly, IPoint2 as _, IntoOverlayResource as _, FILL_RULE};
+
+/// The side of a cut on which a piece of a split area lies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The piece lies to the left of the cut, seen in its direction
+    Left,
+    /// The piece lies to the right of the cut, seen in its direction
+    Right,
+    /// The piece doesn't border the cut, e.g. because the knife ends inside of the area
+    Untouched,
+}
+
+/// Splits areas into pieces along a cut, e.g. to divide a room into two with a drawn line.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let room = Ring::new([Vec2::ZERO, Vec2::new(4.0, 0.0), Vec2::new(4.0, 2.0), Vec2::new(0.0, 2.0)])
+///     .to_polygon();
+///
+/// let pieces = room.split_by_line(&Line([Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0)]));
+///
+/// assert_eq!(pieces.len(), 2);
+/// let (side, left) = &pieces.iter().find(|(side, _)| *side == Side::Left).unwrap();
+/// assert_eq!(*side, Side::Left);
+/// assert!((left.area() - 2.0).abs() < 0.0001);
+///
+/// // a knife which ends inside of the room doesn't cut it
+/// let knife = LineString::new(vec![Vec2::new(2.0, -1.0), Vec2::new(2.0, 1.0)]);
+/// let pieces = room.split_by_linestring(&knife);
+/// assert_eq!(pieces.len(), 1);
+/// assert_eq!(pieces[0].0, Side::Untouched);
+/// ```
+pub trait Split {
+    type P: Point2;
+
+    /// Splits the area along the infinite line through the two points of `line`.
+    fn split_by_line(&self, line: &Line<Self::P>) -> Vec<(Side, Polygon<Self::P>)>;
+
+    /// Cuts the area with a knife along `knife`. The area is only split where the knife cuts
+    /// through it completely. A piece which borders the knife on both sides is reported for the
+    /// side of the first part of the knife it borders.
+    fn split_by_linestring(&self, knife: &LineString<Self::P>) -> Vec<(Side, Polygon<Self::P>)>;
+}
+
+/// Splits flat areas in 3D space by a plane.
+///
+/// The area doesn't need to be perpendicular to the plane: it's split along the line in which its
+/// own plane intersects the cutting plane. If both planes are parallel, the whole area ends up on
+/// one side.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// // a ramp rising along X
+/// let ramp = Ring::new([Vec3::ZERO, Vec3::new(2.0, 0.0, 2.0), Vec3::new(2.0, 1.0, 2.0), Vec3::Y])
+///     .to_polygon();
+/// let plane = Workplane::from_normal_and_origin(Dir3::Z, Vec3::Z);
+///
+/// let (above, below) = ramp.split_by_plane(&plane).unwrap();
+///
+/// assert_eq!(above.len(), 1);
+/// assert_eq!(below.len(), 1);
+/// assert!(above.iter_points().all(|p| p.z >= 0.9999));
+/// assert!(below.iter_points().all(|p| p.z <= 1.0001));
+/// ```
+pub trait SplitByPlane {
+    type P: WorkplanePoint;
+
+    /// The pieces on the side the normal of the plane points to and the pieces on the other side,
+    /// in this order. An area lying in the plane counts as being on the side of the normal. Fails
+    /// if the area is degenerate. `f64` geometry is split by a [`DWorkplane`].
+    #[allow(clippy::type_complexity)]
+    fn split_by_plane(
+        &self,
+        plane: &<Self::P as WorkplanePoint>::Workplane,
+    ) -> Result<(MultiPolygon<Self::P>, MultiPolygon<Self::P>), GeometryError>;
+}
+
+fn slice(area: &MultiPolygon<DVec2>, knife: &[DVec2]) -> Vec<Polygon<DVec2>> {
+    let knife = knife.iter().map(|p| p.to_ipoint()).collect::<Vec<_>>();
+    area.to_overlay_resource()
+        .slice_by(&knife, FILL_RULE)
+        .into_iter()
+        .filter_map(paths_to_poly)
+        .collect()
+}
+
+fn untouched(area: &MultiPolygon<DVec2>) -> Vec<(Side, Polygon<DVec2>)> {
+    area.iter()
+        .map(|polygon| (Side::Untouched, polygon.clone()))
+        .collect()
+}
+
+fn split_by_line(area: &MultiPolygon<DVec2>, [a, b]: [DVec2; 2]) -> Vec<(Side, Polygon<DVec2>)> {
+    let Some(direction) = (b - a).try_normalize() else {
+        return untouched(area);
+    };
+    let (min, max) = area
+        .iter_points()
+        .fold((DVec2::INFINITY, DVec2::NEG_INFINITY), |(min, max), p| {
+            (min.min(p), max.max(p))
+        });
+    if !(min.is_finite() && max.is_finite()) {
+        return vec![];
+    }
+
+    // the line only has to reach across the area
+    let center = (min + max) * 0.5;
+    let reach = (max - min).length() + 1.0;
+    let foot = a + direction * (center - a).dot(direction);
+    let knife = [foot - direction * reach, foot + direction * reach];
+
+    slice(area, &knife)
+        .into_iter()
+        .map(|piece| {
+            let offset = piece
+                .iter_points()
+                .map(|p| direction.perp_dot(p - a))
+                .max_by(|x, y| x.abs().total_cmp(&y.abs()))
+                .unwrap_or_default();
+            let side = if offset > 0.0 {
+                Side::Left
+            } else {
+                Side::Right
+            };
+            (side, piece)
+        })
+        .collect()
+}
+
+fn split_by_linestring(area: &MultiPolygon<DVec2>, knife: &[DVec2]) -> Vec<(Side, Polygon<DVec2>)> {
+    if knife.len() < 2 {
+        return untouched(area);
+    }
+    let (min, max) = area
+        .iter_points()
+        .chain(knife.iter().copied())
+        .fold((DVec2::INFINITY, DVec2::NEG_INFINITY), |(min, max), p| {
+            (min.min(p), max.max(p))
+        });
+    // the slicing snaps to an integer grid spanning the bounds
+    let eps = (max - min).length() * 1e-6;
+
+    slice(area, knife)
+        .into_iter()
+        .map(|piece| {
+            // the interior of a piece is left of its edges, so the direction of an edge running
+            // along the knife tells on which side of the knife the piece is
+            let side = knife
+                .windows(2)
+                .filter_map(|segment| {
+                    let (k, start) = ((segment[1] - segment[0]).try_normalize()?, segment[0]);
+                    let along = |p: DVec2| (p - start).dot(k);
+                    let on_knife = |p: DVec2| k.perp_dot(p - start).abs() <= eps;
+                    let end = along(segment[1]);
+                    piece.lines().find_map(|line| {
+                        let [p, q] = line.0;
+                        let overlaps = on_knife(p)
+                            && on_knife(q)
+                            && along(p).max(along(q)) > eps
+                            && along(p).min(along(q)) < end - eps;
+                        overlaps.then(|| {
+                            if (q - p).dot(k) > 0.0 {
+                                Side::Left
+                            } else {
+                                Side::Right
+                            }
+                        })
+                    })
+                })
+                .next()
+                .unwrap_or(Side::Untouched);
+            (side, piece)
+        })
+        .collect()
+}
+
+impl Split for Polygon<DVec2> {
+    type P = DVec2;
+
+    fn split_by_line(&self, line: &Line<DVec2>) -> Vec<(Side, Polygon<DVec2>)> {
+        split_by_line(&self.to_multi(), line.0)
+    }
+
+    fn split_by_linestring(&self, knife: &LineString<DVec2>) -> Vec<(Side, Polygon<DVec2>)> {
+        split_by_linestring(&self.to_multi(), &knife.0)
+    }
+}
+
+impl Split for MultiPolygon<DVec2> {
+    type P = DVec2;
+
+    fn split_by_line(&self, line: &Line<DVec2>) -> Vec<(Side, Polygon<DVec2>)> {
+        split_by_line(self, line.0)
+    }
+
+    fn split_by_linestring(&self, knife: &LineString<DVec2>) -> Vec<(Side, Polygon<DVec2>)> {
+        split_by_linestring(self, &knife.0)
+    }
+}
+
+fn split_3d<T>(
+    area: &T,
+    plane: &DWorkplane,
+) -> Result<(MultiPolygon<DVec3>, MultiPolygon<DVec3>), GeometryError>
+where
+    T: IterPoints<P = DVec3> + crate::Normal<P = DVec3> + Embed<DWorkplane>,
+    T::Type2D: Split<P = DVec2>,
+{
+    let workplane = DWorkplane::from_primitive(area)?;
+    let injection = workplane.xy_injection();
+
+    // the signed distance to the plane is an affine function on the plane of the area
+    let distance = |p: DVec2| {
+        plane
+            .normal()
+            .dot(injection.transform_point3(p.extend(0.0)) - plane.origin())
+    };
+    let offset = distance(DVec2::ZERO);
+    let gradient = DVec2::new(distance(DVec2::X) - offset, distance(DVec2::Y) - offset);
+
+    let flat = area.embed(workplane);
+    let pieces = if gradient.length() < 1e-6 {
+        let side = if offset >= 0.0 {
+            Side::Left
+        } else {
+            Side::Right
+        };
+        flat.split_by_line(&Line([DVec2::ZERO, DVec2::ZERO]))
+            .into_iter()
+            .map(|(_, piece)| (side, piece))
+            .collect()
+    } else {
+        // seen along the line, the side the gradient points to is on the left
+        let foot = -gradient * offset / gradient.length_squared();
+        flat.split_by_line(&Line([foot, foot - gradient.perp()]))
+    };
+
+    let (mut front, mut back) = (vec![], vec![]);
+    for (side, piece) in pieces {
+        let piece = piece.map(|p| injection.transform_point3(p.extend(0.0)));
+        match side {
+            Side::Left => front.push(piece),
+            _ => back.push(piece),
+        }
+    }
+    Ok((MultiPolygon(front), MultiPolygon(back)))
+}
+
+macro_rules! impl_split {
+    ($($typename:ident),*) => {
+        $(
+            impl Split for $typename<Vec2> {
+                type P = Vec2;
+
+                fn split_by_line(&self, line: &Line<Vec2>) -> Vec<(Side, Polygon<Vec2>)> {
+                    self.map(|p| p.as_dvec2())
+                        .split_by_line(&line.map(|p| p.as_dvec2()))
+                        .into_iter()
+                        .map(|(side, piece)| (side, piece.map(|p| p.as_vec2())))
+                        .collect()
+                }
+
+                fn split_by_linestring(
+                    &self,
+                    knife: &LineString<Vec2>,
+                ) -> Vec<(Side, Polygon<Vec2>)> {
+                    self.map(|p| p.as_dvec2())
+                        .split_by_linestring(&knife.map(|p| p.as_dvec2()))
+                        .into_iter()
+                        .map(|(side, piece)| (side, piece.map(|p| p.as_vec2())))
+                        .collect()
+                }
+            }
+
+            // f32 geometry is split in f64 precision
+            impl SplitByPlane for $typename<Vec3> {
+                type P = Vec3;
+
+                fn split_by_plane(
+                    &self,
+                    plane: &Workplane,
+                ) -> Result<(MultiPolygon<Vec3>, MultiPolygon<Vec3>), GeometryError> {
+                    split_3d(&self.map(|p| p.as_dvec3()), &(*plane).into()).map(
+                        |(front, back)| (front.map(|p| p.as_vec3()), back.map(|p| p.as_vec3())),
+                    )
+                }
+            }
+
+            impl SplitByPlane for $typename<DVec3> {
+                type P = DVec3;
+
+                fn split_by_plane(
+                    &self,
+                    plane: &DWorkplane,
+                ) -> Result<(MultiPolygon<DVec3>, MultiPolygon<DVec3>), GeometryError> {
+                    split_3d(self, plane)
+                }
+            }
+        )*
+    };
+}
+
+impl_split!(Polygon, MultiPolygon);
+
+#[cfg(test)]
+mod split_tests {
+    use bevy_math::Dir3;
+
+    use super::*;
+    use crate::{Area, MultiRing, Ring};
+
+    fn square(size: f32, offset: Vec2) -> Ring<Vec2> {
+        Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p * size + offset))
+    }
+
+    fn area(pieces: &[(Side, Polygon<Vec2>)], side: Side) -> f32 {
+        pieces
+            .iter()
+            .filter(|(s, _)| *s == side)
+            .map(|(_, piece)| piece.area())
+            .sum()
+    }
+
+    #[test]
+    fn line_through_a_hole() {
+        let polygon = Polygon::new(
+            square(3.0, Vec2::ZERO),
+            MultiRing(vec![square(1.0, Vec2::ONE)]),
+        );
+
+        let pieces = polygon.split_by_line(&Line([Vec2::new(0.0, 1.5), Vec2::new(1.0, 1.5)]));
+
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(|(_, piece)| piece.interior().is_empty()));
+        assert!((area(&pieces, Side::Left) - 4.0).abs() < 0.0001);
+        assert!((area(&pieces, Side::Right) - 4.0).abs() < 0.0001);
+
+        let missed = polygon.split_by_line(&Line([Vec2::new(5.0, 0.0), Vec2::new(5.0, 1.0)]));
+        assert_eq!(missed.len(), 1);
+        assert_eq!(missed[0].0, Side::Left);
+    }
+
+    #[test]
+    fn knife_along_an_l_shaped_path() {
+        //  ┌───────┐
+        //  │   ┊   │
+        //  │   └┄┄┄┼┄┄
+        //  │       │
+        //  └───────┘
+        let room = square(4.0, Vec2::ZERO).to_polygon().to_multi();
+        let knife = LineString::new(vec![
+            Vec2::new(2.0, 5.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(5.0, 2.0),
+        ]);
+
+        let pieces = room.split_by_linestring(&knife);
+
+        assert_eq!(pieces.len(), 2);
+        assert!((area(&pieces, Side::Left) - 4.0).abs() < 0.0001);
+        assert!((area(&pieces, Side::Right) - 12.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn plane_parallel_to_the_polygon() {
+        let floor =
+            Ring::new([Vec3::ZERO, Vec3::X, Vec3::new(1.0, 1.0, 0.0), Vec3::Y]).to_polygon();
+
+        let below = Workplane::from_normal_and_origin(Dir3::Z, Vec3::NEG_Z);
+        let (front, back) = floor.split_by_plane(&below).unwrap();
+        assert_eq!((front.len(), back.len()), (1, 0));
+
+        let above = Workplane::from_normal_and_origin(Dir3::Z, Vec3::Z);
+        let (front, back) = floor.split_by_plane(&above).unwrap();
+        assert_eq!((front.len(), back.len()), (0, 1));
+        assert!(back.area().abs_diff_eq(floor.area(), 0.0001));
+    }
+
+    #[test]
+    fn wall_cut_by_a_vertical_plane() {
+        let wall = Ring::new([
+            Vec3::ZERO,
+            Vec3::new(4.0, 0.0, 0.0),
+            Vec3::new(4.0, 0.0, 3.0),
+            Vec3::new(0.0, 0.0, 3.0),
+        ])
+        .to_polygon()
+        .map(|p| p.as_dvec3());
+        let plane = DWorkplane::from_normal_and_origin(DVec3::NEG_X, DVec3::X).unwrap();
+
+        let (front, back) = wall.split_by_plane(&plane).unwrap();
+
+        assert_eq!((front.len(), back.len()), (1, 1));
+        assert!((front.area().length() - 3.0).abs() < 0.0001);
+        assert!((back.area().length() - 9.0).abs() < 0.0001);
+        assert!(front.iter_points().all(|p| p.x <= 1.0001));
+    }
+
+    #[test]
+    fn ramp_far_from_the_origin() {
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        let ramp = Ring::new(
+            [
+                DVec3::ZERO,
+                DVec3::new(2.0, 0.0, 2.0),
+                DVec3::new(2.0, 1.0, 2.0),
+                DVec3::Y,
+            ]
+            .map(|p| p + offset),
+        )
+        .to_polygon();
+        let plane = DWorkplane::from_normal_and_origin(DVec3::Z, offset + DVec3::Z * 0.75).unwrap();
+
+        let (above, below) = ramp.split_by_plane(&plane).unwrap();
+
+        assert_eq!((above.len(), below.len()), (1, 1));
+        // single precision only has steps of half a unit this far from the origin
+        let sqrt_2 = 2f64.sqrt();
+        assert!((above.area().length() - 1.25 * sqrt_2).abs() < 1e-5);
+        assert!((below.area().length() - 0.75 * sqrt_2).abs() < 1e-5);
+        assert!(above.iter_points().all(|p| p.z - offset.z >= 0.75 - 1e-5));
+        assert!(below.iter_points().all(|p| p.z - offset.z <= 0.75 + 1e-5));
+    }
+}
@@ -0,0 +1,309 @@
+use std::ops::Neg;
+
+use bevy_math::{DVec2, DVec3, Dir3, Vec2, Vec3};
+
+use crate::{
+    algorithms::stitch_triangles,
+    prelude::{DWorkplane, StitchError, Workplane},
+    primitives::*,
+    Area, Embed, Map, Point, Point3, Unembed, Wedge,
+};
+
+/// Reconstructs the polygons covered by a triangulation, e.g. the output of
+/// [`Triangulate`](crate::Triangulate).
+///
+/// Vertices which are closer than `tolerance` are merged and edges are split where a vertex of
+/// another triangle lies on them. Triangles are allowed to have any orientation, holes in the
+/// triangulation become holes of the polygons. Edges which are shared by more than two triangles
+/// or by two overlapping triangles are reported as [`StitchError::NonManifoldEdges`].
+///
+/// In 3D all triangles have to lie within `tolerance` of a common plane.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let triangles = MultiTriangle(vec![
+///     Triangle([Vec2::ZERO, Vec2::X, Vec2::ONE]),
+///     Triangle([Vec2::ZERO, Vec2::ONE, Vec2::Y]),
+/// ]);
+///
+/// let square = triangles.stitch(0.001).unwrap();
+///
+/// assert_eq!(square.len(), 1);
+/// assert_eq!(square[0].exterior().points_open().len(), 4);
+///
+/// // a third triangle on top of the first one
+/// let overlapping = MultiTriangle(vec![
+///     triangles.0[0],
+///     triangles.0[1],
+///     Triangle([Vec2::ZERO, Vec2::X, Vec2::new(0.5, 0.5)]),
+/// ]);
+/// assert!(overlapping.stitch(0.001).is_err());
+/// ```
+pub trait Stitch {
+    type P: Point;
+
+    fn stitch(&self, tolerance: f64) -> Result<MultiPolygon<Self::P>, StitchError<Self::P>>;
+}
+
+impl Stitch for MultiTriangle<DVec2> {
+    type P = DVec2;
+
+    fn stitch(&self, tolerance: f64) -> Result<MultiPolygon<DVec2>, StitchError<DVec2>> {
+        stitch_triangles(&self.0, tolerance).map_err(StitchError::NonManifoldEdges)
+    }
+}
+
+impl Stitch for MultiTriangle<Vec2> {
+    type P = Vec2;
+
+    fn stitch(&self, tolerance: f64) -> Result<MultiPolygon<Vec2>, StitchError<Vec2>> {
+        self.map(|p| p.as_dvec2())
+            .stitch(tolerance)
+            .map(|polygons| polygons.map(|p| p.as_vec2()))
+            .map_err(|error| error.map(|p| p.as_vec2()))
+    }
+}
+
+/// The sum of the normals of the triangles. Triangles facing the other way than the first one
+/// which isn't degenerate are flipped, so that they don't cancel the normal out.
+fn consistent_normal<P>(triangles: &MultiTriangle<P>) -> P
+where
+    P: Point3 + Wedge<Output = P> + Neg<Output = P>,
+    Triangle<P>: Area<P = P>,
+{
+    let zero = <P::S>::from(0.0);
+    let reference = triangles
+        .0
+        .iter()
+        .map(|t| t.area())
+        .find(|n| n.dot(*n) > zero);
+    triangles
+        .0
+        .iter()
+        .map(|t| t.area())
+        .map(|n| {
+            if reference.is_some_and(|r| r.dot(n) < zero) {
+                -n
+            } else {
+                n
+            }
+        })
+        .sum()
+}
+
+impl Stitch for MultiTriangle<Vec3> {
+    type P = Vec3;
+
+    fn stitch(&self, tolerance: f64) -> Result<MultiPolygon<Vec3>, StitchError<Vec3>> {
+        let Some(origin) = self.0.first().map(|t| t.0[0]) else {
+            return Ok(MultiPolygon::empty());
+        };
+        let Ok(normal) = Dir3::new(consistent_normal(self)) else {
+            return Ok(MultiPolygon::empty());
+        };
+        let workplane = Workplane::from_normal_and_origin(normal, origin);
+        let coplanar = self
+            .0
+            .iter()
+            .flat_map(|t| t.0)
+            .all(|p| (normal.dot(p - workplane.origin()) as f64).abs() <= tolerance);
+        if !coplanar {
+            return Err(StitchError::NotCoplanar);
+        }
+
+        let triangles: MultiTriangle<Vec2> = self.embed(workplane);
+        triangles
+            .stitch(tolerance)
+            .map(|polygons| polygons.unembed(workplane))
+            .map_err(|error| {
+                error.map(|p| workplane.xy_injection().transform_point3(p.extend(0.0)))
+            })
+    }
+}
+
+impl Stitch for MultiTriangle<DVec3> {
+    type P = DVec3;
+
+    fn stitch(&self, tolerance: f64) -> Result<MultiPolygon<DVec3>, StitchError<DVec3>> {
+        let Some(origin) = self.0.first().map(|t| t.0[0]) else {
+            return Ok(MultiPolygon::empty());
+        };
+        let Ok(workplane) = DWorkplane::from_normal_and_origin(consistent_normal(self), origin)
+        else {
+            return Ok(MultiPolygon::empty());
+        };
+        let coplanar = self
+            .0
+            .iter()
+            .flat_map(|t| t.0)
+            .all(|p| workplane.normal().dot(p - workplane.origin()).abs() <= tolerance);
+        if !coplanar {
+            return Err(StitchError::NotCoplanar);
+        }
+
+        let triangles: MultiTriangle<DVec2> = self.embed(workplane);
+        triangles
+            .stitch(tolerance)
+            .map(|polygons| polygons.unembed(workplane))
+            .map_err(|error| {
+                error.map(|p| workplane.xy_injection().transform_point3(p.extend(0.0)))
+            })
+    }
+}
+
+impl<P: Point> StitchError<P> {
+    /// Maps the points of the reported edges
+    pub fn map<Q: Point>(self, f: impl Fn(P) -> Q) -> StitchError<Q> {
+        match self {
+            StitchError::NonManifoldEdges(edges) => StitchError::NonManifoldEdges(
+                edges.into_iter().map(|edge| Line(edge.0.map(&f))).collect(),
+            ),
+            StitchError::NotCoplanar => StitchError::NotCoplanar,
+        }
+    }
+}
+
+#[cfg(test)]
+mod stitch_tests {
+    use super::*;
+
+    /// A 3x3 grid of unit squares without the center, each split into two triangles
+    fn frame() -> MultiTriangle<Vec2> {
+        MultiTriangle(
+            (0..3)
+                .flat_map(|x| (0..3).map(move |y| Vec2::new(x as f32, y as f32)))
+                .filter(|p| *p != Vec2::ONE)
+                .flat_map(|p| {
+                    [
+                        Triangle([p, p + Vec2::X, p + Vec2::ONE]),
+                        // clockwise on purpose
+                        Triangle([p, p + Vec2::Y, p + Vec2::ONE]),
+                    ]
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn keeps_holes() {
+        let polygons = frame().stitch(0.001).unwrap();
+
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].interior().len(), 1);
+        assert!((polygons[0].exterior().area() - 9.0).abs() < 0.0001);
+        assert!((polygons[0].interior()[0].area() + 1.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn merges_near_vertices_and_t_junctions() {
+        let noise = Vec2::new(0.0003, -0.0002);
+        let triangles = MultiTriangle(vec![
+            // the long edge of this triangle touches two smaller ones
+            Triangle([Vec2::ZERO, Vec2::new(2.0, 0.0), Vec2::new(1.0, -1.0)]),
+            Triangle([Vec2::ZERO, Vec2::new(1.0, 1.0), Vec2::new(1.0, 0.0) + noise]),
+            Triangle([
+                Vec2::new(1.0, 0.0),
+                Vec2::new(1.0, 1.0),
+                Vec2::new(2.0, 0.0),
+            ]),
+        ]);
+
+        let polygons = triangles.stitch(0.001).unwrap();
+
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].exterior().points_open().len(), 4);
+    }
+
+    #[test]
+    fn welds_signed_zeros_without_tolerance() {
+        let triangles = MultiTriangle(vec![
+            Triangle([Vec2::ZERO, Vec2::X, Vec2::Y]),
+            Triangle([Vec2::new(-0.0, 1.0), Vec2::X, Vec2::ONE]),
+        ]);
+
+        let polygons = triangles.stitch(0.0).unwrap();
+
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].exterior().points_open().len(), 4);
+    }
+
+    #[test]
+    fn squares_touching_in_a_corner_stay_apart() {
+        let square = |offset: Vec2| {
+            let [a, b, c, d] = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p + offset);
+            [Triangle([a, b, c]), Triangle([a, c, d])]
+        };
+        let triangles = MultiTriangle([square(Vec2::ZERO), square(Vec2::ONE)].concat());
+
+        let polygons = triangles.stitch(0.001).unwrap();
+
+        // taking the sharpest left turn at the shared corner closes each square on its own
+        assert_eq!(polygons.len(), 2);
+        assert!(polygons
+            .iter()
+            .all(|polygon| polygon.exterior().points_open().len() == 4));
+    }
+
+    #[test]
+    fn reports_non_manifold_edges() {
+        let mut triangles = frame();
+        // a flap sticking out of the edge between (0, 0) and (1, 0)
+        triangles
+            .0
+            .push(Triangle([Vec2::ZERO, Vec2::X, Vec2::new(0.5, 0.5)]));
+
+        let Err(StitchError::NonManifoldEdges(edges)) = triangles.stitch(0.001) else {
+            panic!("expected non-manifold edges");
+        };
+
+        assert!(edges.contains(&Line([Vec2::ZERO, Vec2::X])));
+    }
+
+    #[test]
+    fn stitch_in_3d() {
+        let to_3d = |p: Vec2| Vec3::new(p.x, p.y, p.y);
+        let triangles = frame().map(to_3d);
+
+        let polygons = triangles.stitch(0.001).unwrap();
+
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].interior().len(), 1);
+        let bent = MultiTriangle(vec![
+            Triangle([Vec3::ZERO, Vec3::X, Vec3::Y]),
+            Triangle([Vec3::X, Vec3::Y, Vec3::Z]),
+        ]);
+        assert!(matches!(bent.stitch(0.001), Err(StitchError::NotCoplanar)));
+    }
+
+    #[test]
+    fn stitch_in_3d_far_from_the_origin() {
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        let to_3d = |p: Vec2| DVec3::new(p.x as f64, p.y as f64, p.y as f64) + offset;
+        let triangles = frame().map(to_3d);
+
+        let polygons = triangles.stitch(1e-9).unwrap();
+
+        assert_eq!(polygons.len(), 1);
+        assert_eq!(polygons[0].interior().len(), 1);
+        // the vertices along the edges of the grid are kept
+        assert_eq!(polygons[0].exterior().points_open().len(), 12);
+        for p in polygons[0].iter_rings().flat_map(|ring| ring.points_open()) {
+            let local = *p - offset;
+            assert!(local.round().abs_diff_eq(local, 1e-9), "{local}");
+        }
+    }
+
+    #[test]
+    fn empty_input_in_3d() {
+        assert!(MultiTriangle::<Vec3>(vec![])
+            .stitch(0.1)
+            .unwrap()
+            .is_empty());
+        assert!(MultiTriangle::<DVec3>(vec![])
+            .stitch(0.1)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use bevy_math::{DVec2, DVec3, Vec2, Vec3};
+
+use crate::{
+    algorithms::{weighted_straight_skeleton, SkeletonGraph},
+    errors::GeometryError,
+    prelude::{DWorkplane, Workplane},
+    primitives::*,
+    Embed, Map, Point,
+};
+
+/// The straight skeleton of an area, i.e. the paths the vertices take while all edges move inwards
+/// with the same speed until the area vanishes.
+///
+/// In contrast to [`skeleton_lines_glam`](crate::skeleton_lines_glam) the result is a
+/// [`SkeletonGraph`] which contains the time at which every node is reached and the face every
+/// input edge sweeps.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let rectangle = Ring::new([Vec2::ZERO, Vec2::X * 4.0, Vec2::new(4.0, 2.0), Vec2::Y * 2.0]);
+///
+/// let skeleton = rectangle.to_polygon().straight_skeleton().unwrap();
+///
+/// // the ridge in the middle of the rectangle is reached after one unit of time
+/// let ridge = skeleton.nodes.iter().filter(|node| node.time > 0.0).collect::<Vec<_>>();
+/// assert_eq!(ridge.len(), 2);
+/// assert!(ridge.iter().all(|node| (node.time - 1.0).abs() < 0.0001));
+/// // one face per edge
+/// assert_eq!(skeleton.faces.len(), 4);
+/// ```
+pub trait StraightSkeleton {
+    type P: Point;
+
+    /// Fails with [`GeometryError::InvalidGeometry`] for rings with repeated consecutive points and
+    /// with [`GeometryError::NotConverged`] if the wavefront doesn't collapse, which only happens
+    /// for degenerate input. Areas without a plane in 3D have an empty skeleton.
+    fn straight_skeleton(&self) -> Result<SkeletonGraph<Self::P>, GeometryError>;
+}
+
+impl StraightSkeleton for Polygon<DVec2> {
+    type P = DVec2;
+
+    fn straight_skeleton(&self) -> Result<SkeletonGraph<DVec2>, GeometryError> {
+        weighted_straight_skeleton(self, |_| 1.0)
+    }
+}
+
+impl StraightSkeleton for MultiPolygon<DVec2> {
+    type P = DVec2;
+
+    fn straight_skeleton(&self) -> Result<SkeletonGraph<DVec2>, GeometryError> {
+        self.0
+            .iter()
+            .try_fold(SkeletonGraph::default(), |mut graph, polygon| {
+                graph.append(polygon.straight_skeleton()?);
+                Ok(graph)
+            })
+    }
+}
+
+impl StraightSkeleton for Polygon<Vec2> {
+    type P = Vec2;
+
+    fn straight_skeleton(&self) -> Result<SkeletonGraph<Vec2>, GeometryError> {
+        self.map(|p| p.as_dvec2())
+            .straight_skeleton()
+            .map(|graph| graph.map(|p| p.as_vec2(), |t| t as f32))
+    }
+}
+
+impl StraightSkeleton for MultiPolygon<Vec2> {
+    type P = Vec2;
+
+    fn straight_skeleton(&self) -> Result<SkeletonGraph<Vec2>, GeometryError> {
+        self.map(|p| p.as_dvec2())
+            .straight_skeleton()
+            .map(|graph| graph.map(|p| p.as_vec2(), |t| t as f32))
+    }
+}
+
+macro_rules! impl_straight_skeleton_3d {
+    ($($typename:ident),*) => {
+        $(
+            impl_straight_skeleton_3d!(@point $typename; Vec3; Workplane);
+            impl_straight_skeleton_3d!(@point $typename; DVec3; DWorkplane);
+        )*
+    };
+    (@point $typename:ident; $point:ty; $workplane:ty) => {
+        impl StraightSkeleton for $typename<$point> {
+            type P = $point;
+
+            fn straight_skeleton(&self) -> Result<SkeletonGraph<$point>, GeometryError> {
+                let Ok(wp) = <$workplane>::from_primitive(self) else {
+                    return Ok(SkeletonGraph::default());
+                };
+                let injection = wp.xy_injection();
+                self.embed(wp)
+                    .straight_skeleton()
+                    .map(|graph| graph.map(|p| injection.transform_point3(p.extend(0.0)), |t| t))
+            }
+        }
+    };
+}
+
+impl_straight_skeleton_3d!(Polygon, MultiPolygon);
+
+#[cfg(test)]
+mod straight_skeleton_tests {
+    use super::*;
+
+    #[test]
+    fn skeleton_in_3d_far_from_the_origin() {
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        // a vertical rectangle, the ridge runs along its middle at half its height
+        let wall = Ring::new(
+            [
+                DVec3::ZERO,
+                DVec3::X * 4.0,
+                DVec3::new(4.0, 0.0, 2.0),
+                DVec3::Z * 2.0,
+            ]
+            .map(|p| p + offset),
+        );
+
+        let skeleton = wall.to_polygon().straight_skeleton().unwrap();
+
+        let ridge = skeleton
+            .nodes
+            .iter()
+            .filter(|node| node.time > 0.0)
+            .collect::<Vec<_>>();
+        assert_eq!(ridge.len(), 2);
+        for node in ridge {
+            assert!((node.time - 1.0).abs() < 1e-9);
+            let local = node.position - offset;
+            assert!(
+                local.y.abs() < 1e-9 && (local.z - 1.0).abs() < 1e-9,
+                "{local}"
+            );
+            assert!(
+                (local.x - 1.0).abs() < 1e-9 || (local.x - 3.0).abs() < 1e-9,
+                "{local}"
+            );
+        }
+    }
+}
@@ -0,0 +1,629 @@
+use bevy_math::{DVec2, DVec3, Vec2, Vec3};
+
+use crate::{
+    algorithms::{triangulate_polygon, IndexedTriangles},
+    errors::GeometryError,
+    prelude::{DWorkplane, Workplane},
+    primitives::*,
+    Area, Embed, IterPoints, Map, Point,
+};
+
+/// The algorithm used by [`Triangulate`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TriangulationMethod {
+    /// Fast and only uses the vertices of the input, but may produce thin triangles
+    EarClipping,
+    /// Maximizes the minimal angle of the triangles while keeping the edges of the input
+    #[default]
+    ConstrainedDelaunay,
+    /// Adds vertices to a constrained Delaunay triangulation until all triangles are well-shaped,
+    /// e.g. for simulations which can't deal with thin triangles
+    Refined(RefinementConfig),
+}
+
+/// Bounds for the triangles of [`TriangulationMethod::Refined`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefinementConfig {
+    /// The smallest allowed angle of a triangle in radians. Angles above 30° may not be reachable
+    pub min_angle: f64,
+    /// The largest allowed area of a triangle, [`f64::INFINITY`] for no limit
+    pub max_area: f64,
+    /// Fails with [`GeometryError::NotConverged`] if the bounds aren't met after adding this many
+    /// vertices. Defaults to ten times the number of vertices of the input.
+    pub max_additional_vertices: Option<usize>,
+}
+
+impl Default for RefinementConfig {
+    fn default() -> Self {
+        Self {
+            min_angle: 30f64.to_radians(),
+            max_area: f64::INFINITY,
+            max_additional_vertices: None,
+        }
+    }
+}
+
+/// Configuration of [`Triangulate`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangulationConfig {
+    pub method: TriangulationMethod,
+    /// Vertices closer to each other than this distance are merged
+    pub snap_radius: f64,
+}
+
+impl Default for TriangulationConfig {
+    fn default() -> Self {
+        Self {
+            method: TriangulationMethod::default(),
+            snap_radius: 0.001,
+        }
+    }
+}
+
+/// The position of a vertex in the input of [`Triangulate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RingVertex {
+    /// Index of the polygon, always `0` unless a [`MultiPolygon`] was triangulated
+    pub polygon: usize,
+    /// Index of the ring in [`Polygon::iter_rings`], i.e. `0` is the exterior
+    pub ring: usize,
+    /// Index of the vertex in [`Ring::points_open`]
+    pub vertex: usize,
+}
+
+/// Triangles sharing their vertices, e.g. for uploading them to the GPU.
+///
+/// The first vertices are the ones of the input in their original order, so `vertices` can be
+/// used as a vertex buffer directly. Vertices which were added by the triangulation come last and
+/// have no [`RingVertex`] in `sources`.
+#[derive(Debug, Clone)]
+pub struct IndexedMesh<P: Point> {
+    pub vertices: Vec<P>,
+    pub indices: Vec<[u32; 3]>,
+    /// Where every vertex comes from, has the same length as `vertices`
+    pub sources: Vec<Option<RingVertex>>,
+}
+
+impl<P: Point> Default for IndexedMesh<P> {
+    fn default() -> Self {
+        Self {
+            vertices: vec![],
+            indices: vec![],
+            sources: vec![],
+        }
+    }
+}
+
+impl<P: Point> IndexedMesh<P> {
+    /// Resolves the indices to a [`MultiTriangle`] with duplicated vertices
+    pub fn to_triangles(&self) -> MultiTriangle<P> {
+        MultiTriangle(
+            self.indices
+                .iter()
+                .map(|triangle| Triangle(triangle.map(|i| self.vertices[i as usize])))
+                .collect(),
+        )
+    }
+
+    /// Maps the vertices while keeping the indices and sources
+    pub fn map_vertices<Q: Point>(
+        self,
+        mut f: impl FnMut(P, Option<RingVertex>) -> Q,
+    ) -> IndexedMesh<Q> {
+        IndexedMesh {
+            vertices: self
+                .vertices
+                .into_iter()
+                .zip(&self.sources)
+                .map(|(p, source)| f(p, *source))
+                .collect(),
+            indices: self.indices,
+            sources: self.sources,
+        }
+    }
+
+    /// Appends the mesh of another polygon
+    fn append(&mut self, other: IndexedMesh<P>, polygon: usize) -> Result<(), GeometryError> {
+        let offset = to_index(self.vertices.len())?;
+        to_index(self.vertices.len() + other.vertices.len())?;
+        self.vertices.extend(other.vertices);
+        self.indices.extend(
+            other
+                .indices
+                .into_iter()
+                .map(|triangle| triangle.map(|i| i + offset)),
+        );
+        self.sources.extend(
+            other
+                .sources
+                .into_iter()
+                .map(|source| source.map(|source| RingVertex { polygon, ..source })),
+        );
+        Ok(())
+    }
+}
+
+fn to_index(i: usize) -> Result<u32, GeometryError> {
+    u32::try_from(i).map_err(|_| GeometryError::InvalidGeometry)
+}
+
+/// Splits an area into triangles.
+///
+/// The triangles have the same orientation as the exterior of the area, in 3D their normals point
+/// in the direction of the normal of the area. Areas without extent result in no triangles, an
+/// error is only returned for areas which can't be triangulated, e.g. because they intersect
+/// themselves or contain non-finite coordinates.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let square = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]);
+///
+/// let config = TriangulationConfig {
+///     method: TriangulationMethod::EarClipping,
+///     ..Default::default()
+/// };
+/// let triangles = square.triangulate(config).unwrap();
+/// assert_eq!(triangles.0.len(), 2);
+///
+/// // the indexed mesh shares the vertices of the ring
+/// let mesh = square.triangulate_indexed(config).unwrap();
+/// assert_eq!(mesh.vertices, square.points_open());
+/// assert_eq!(mesh.indices.len(), 2);
+/// ```
+pub trait Triangulate {
+    type P: Point;
+
+    /// Triangulates into an [`IndexedMesh`] which references the vertices of the input
+    fn triangulate_indexed(
+        &self,
+        config: TriangulationConfig,
+    ) -> Result<IndexedMesh<Self::P>, GeometryError>;
+
+    fn triangulate(
+        &self,
+        config: TriangulationConfig,
+    ) -> Result<MultiTriangle<Self::P>, GeometryError> {
+        self.triangulate_indexed(config)
+            .map(|mesh| mesh.to_triangles())
+    }
+}
+
+/// Looks up the vertex a [`RingVertex`] refers to
+trait VertexLookup<P> {
+    fn vertex(&self, source: RingVertex) -> P;
+}
+
+impl<P: Point> VertexLookup<P> for Ring<P> {
+    fn vertex(&self, source: RingVertex) -> P {
+        self.points_open()[source.vertex]
+    }
+}
+
+impl<P: Point> VertexLookup<P> for Polygon<P> {
+    fn vertex(&self, source: RingVertex) -> P {
+        self.iter_rings()
+            .nth(source.ring)
+            .map(|ring| ring.points_open()[source.vertex])
+            .expect("sources refer to the triangulated polygon")
+    }
+}
+
+impl<P: Point> VertexLookup<P> for MultiPolygon<P> {
+    fn vertex(&self, source: RingVertex) -> P {
+        self.0[source.polygon].vertex(source)
+    }
+}
+
+impl Triangulate for Polygon<DVec2> {
+    type P = DVec2;
+
+    fn triangulate_indexed(
+        &self,
+        config: TriangulationConfig,
+    ) -> Result<IndexedMesh<DVec2>, GeometryError> {
+        let IndexedTriangles {
+            vertices,
+            triangles,
+        } = triangulate_polygon(self, config)?;
+        to_index(vertices.len())?;
+
+        let clockwise = self.exterior().area() < 0.0;
+        let mut sources = self
+            .iter_rings()
+            .enumerate()
+            .flat_map(|(ring, points)| {
+                (0..points.points_open().len()).map(move |vertex| {
+                    Some(RingVertex {
+                        polygon: 0,
+                        ring,
+                        vertex,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        sources.resize(vertices.len(), None);
+
+        Ok(IndexedMesh {
+            vertices,
+            indices: triangles
+                .into_iter()
+                .map(|[a, b, c]| {
+                    let triangle = if clockwise { [a, c, b] } else { [a, b, c] };
+                    triangle.map(|i| i as u32)
+                })
+                .collect(),
+            sources,
+        })
+    }
+}
+
+impl Triangulate for Ring<DVec2> {
+    type P = DVec2;
+
+    fn triangulate_indexed(
+        &self,
+        config: TriangulationConfig,
+    ) -> Result<IndexedMesh<DVec2>, GeometryError> {
+        self.to_polygon().triangulate_indexed(config)
+    }
+}
+
+impl Triangulate for MultiPolygon<DVec2> {
+    type P = DVec2;
+
+    fn triangulate_indexed(
+        &self,
+        config: TriangulationConfig,
+    ) -> Result<IndexedMesh<DVec2>, GeometryError> {
+        let mut mesh = IndexedMesh::default();
+        for (i, polygon) in self.iter().enumerate() {
+            mesh.append(polygon.triangulate_indexed(config)?, i)?;
+        }
+        Ok(mesh)
+    }
+}
+
+macro_rules! impl_triangulate_f32 {
+    ($($typename:ident),*) => {
+        $(
+            impl Triangulate for $typename<Vec2> {
+                type P = Vec2;
+
+                fn triangulate_indexed(
+                    &self,
+                    config: TriangulationConfig,
+                ) -> Result<IndexedMesh<Vec2>, GeometryError> {
+                    self.map(|p: Vec2| p.as_dvec2())
+                        .triangulate_indexed(config)
+                        .map(|mesh| mesh.map_vertices(|p, _| p.as_vec2()))
+                }
+            }
+        )*
+    };
+}
+
+impl_triangulate_f32!(Ring, Polygon, MultiPolygon);
+
+macro_rules! impl_triangulate_3d {
+    ($($typename:ident),*) => {
+        $(
+            impl Triangulate for $typename<Vec3> {
+                type P = Vec3;
+
+                fn triangulate_indexed(
+                    &self,
+                    config: TriangulationConfig,
+                ) -> Result<IndexedMesh<Vec3>, GeometryError> {
+                    let workplane = match Workplane::from_primitive(self) {
+                        Ok(workplane) => workplane,
+                        // areas without extent have no plane
+                        Err(_) if self.iter_points().all(|p| p.is_finite()) => {
+                            return Ok(IndexedMesh::default());
+                        }
+                        Err(error) => return Err(error),
+                    };
+                    let injection = workplane.xy_injection();
+                    self.embed(workplane)
+                        .map(|p: Vec2| p.as_dvec2())
+                        .triangulate_indexed(config)
+                        .map(|mesh| {
+                            // vertices of the input keep their exact position
+                            mesh.map_vertices(|p, source| match source {
+                                Some(source) => self.vertex(source),
+                                None => injection.transform_point3(p.as_vec2().extend(0.0)),
+                            })
+                        })
+                }
+            }
+
+            impl Triangulate for $typename<DVec3> {
+                type P = DVec3;
+
+                fn triangulate_indexed(
+                    &self,
+                    config: TriangulationConfig,
+                ) -> Result<IndexedMesh<DVec3>, GeometryError> {
+                    let workplane = match DWorkplane::from_primitive(self) {
+                        Ok(workplane) => workplane,
+                        // areas without extent have no plane
+                        Err(_) if self.iter_points().all(|p| p.is_finite()) => {
+                            return Ok(IndexedMesh::default());
+                        }
+                        Err(error) => return Err(error),
+                    };
+                    let injection = workplane.xy_injection();
+                    self.embed(workplane)
+                        .triangulate_indexed(config)
+                        .map(|mesh| {
+                            mesh.map_vertices(|p, source| match source {
+                                Some(source) => self.vertex(source),
+                                None => injection.transform_point3(p.extend(0.0)),
+                            })
+                        })
+                }
+            }
+        )*
+    };
+}
+
+impl_triangulate_3d!(Ring, Polygon, MultiPolygon);
+
+#[cfg(test)]
+mod triangulate_tests {
+    use super::*;
+
+    fn config(method: TriangulationMethod) -> TriangulationConfig {
+        TriangulationConfig {
+            method,
+            ..Default::default()
+        }
+    }
+
+    const METHODS: [TriangulationMethod; 3] = [
+        TriangulationMethod::EarClipping,
+        TriangulationMethod::ConstrainedDelaunay,
+        TriangulationMethod::Refined(RefinementConfig {
+            min_angle: 0.4,
+            max_area: f64::INFINITY,
+            max_additional_vertices: None,
+        }),
+    ];
+
+    fn area(triangles: &MultiTriangle<Vec2>) -> f32 {
+        triangles.0.iter().map(|triangle| triangle.area()).sum()
+    }
+
+    #[test]
+    fn polygon_with_holes() {
+        let square = |size: f32, offset: Vec2| {
+            Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p * size + offset))
+        };
+        let polygon = Polygon::new(
+            square(10.0, Vec2::ZERO),
+            MultiRing(vec![
+                square(2.0, Vec2::splat(2.0)),
+                square(2.0, Vec2::new(6.0, 2.0)),
+                square(2.0, Vec2::new(4.0, 6.0)),
+            ]),
+        );
+
+        for method in METHODS {
+            let triangles = polygon.triangulate(config(method)).unwrap();
+
+            assert!((area(&triangles) - 88.0).abs() < 0.001, "{method:?}");
+            assert!(triangles.0.iter().all(|triangle| triangle.area() > 0.0));
+        }
+    }
+
+    #[test]
+    fn concave_ring_keeps_orientation() {
+        // a clockwise comb
+        let ring = Ring::new(vec![
+            Vec2::new(0.0, 3.0),
+            Vec2::new(5.0, 3.0),
+            Vec2::new(5.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(4.0, 2.0),
+            Vec2::new(3.0, 2.0),
+            Vec2::new(3.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 0.0),
+        ]);
+
+        for method in METHODS {
+            let triangles = ring.triangulate(config(method)).unwrap();
+
+            assert!((area(&triangles) + 11.0).abs() < 0.001, "{method:?}");
+            assert!(triangles.0.iter().all(|triangle| triangle.area() < 0.0));
+        }
+    }
+
+    #[test]
+    fn degenerate_input() {
+        let collinear = Ring::new([Vec2::ZERO, Vec2::X, Vec2::X * 2.0]);
+        let tiny = Ring::new([Vec2::ZERO, Vec2::X * 0.0001, Vec2::Y * 0.0001]);
+
+        for method in METHODS {
+            assert!(collinear.triangulate(config(method)).unwrap().0.is_empty());
+            assert!(tiny.triangulate(config(method)).unwrap().0.is_empty());
+            let nan = Ring::new([Vec2::ZERO, Vec2::X, Vec2::NAN]);
+            assert!(nan.triangulate(config(method)).is_err());
+        }
+    }
+
+    #[test]
+    fn degenerate_input_in_3d() {
+        let collinear = Ring::new([Vec3::ZERO, Vec3::X, Vec3::X * 2.0]).to_polygon();
+        let point = Ring::new([DVec3::ONE; 3]).to_polygon();
+
+        for method in METHODS {
+            assert!(collinear.triangulate(config(method)).unwrap().0.is_empty());
+            assert!(point.triangulate(config(method)).unwrap().0.is_empty());
+            let nan = Ring::new([Vec3::ZERO, Vec3::X, Vec3::NAN]).to_polygon();
+            assert!(nan.triangulate(config(method)).is_err());
+            let nan = Ring::new([DVec3::ZERO, DVec3::X, DVec3::NAN]).to_polygon();
+            assert!(nan.triangulate(config(method)).is_err());
+        }
+    }
+
+    #[test]
+    fn triangles_in_3d_follow_the_normal() {
+        let ring = Ring::new([Vec3::ZERO, Vec3::X, Vec3::new(1.0, 0.0, 1.0), Vec3::Z]);
+
+        for method in METHODS {
+            let triangles = ring.triangulate(config(method)).unwrap();
+
+            let normal = triangles.0.iter().map(|t| t.area()).sum::<Vec3>();
+            assert!(normal.abs_diff_eq(ring.area(), 0.0001), "{method:?}");
+        }
+    }
+
+    #[test]
+    fn indexed_mesh_references_the_input() {
+        let polygon = Polygon::new(
+            Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p * 4.0)),
+            MultiRing(vec![Ring::new(
+                [Vec2::ZERO, Vec2::Y, Vec2::ONE, Vec2::X].map(|p| p + 1.0),
+            )]),
+        );
+        let multi = MultiPolygon(vec![polygon.clone(), polygon.clone()]);
+
+        // refinement adds vertices
+        for method in [
+            TriangulationMethod::EarClipping,
+            TriangulationMethod::ConstrainedDelaunay,
+        ] {
+            let mesh = multi.triangulate_indexed(config(method)).unwrap();
+
+            assert_eq!(mesh.vertices.len(), 16, "{method:?}");
+            assert_eq!(mesh.sources.len(), 16);
+            for (p, source) in mesh.vertices.iter().zip(&mesh.sources) {
+                let source = source.unwrap();
+                let ring = polygon.iter_rings().nth(source.ring).unwrap();
+                assert_eq!(*p, ring.points_open()[source.vertex]);
+            }
+            assert_eq!(mesh.sources[12].unwrap().polygon, 1);
+            // every triangle stays within its polygon
+            assert!(mesh.indices.iter().all(
+                |triangle| triangle.iter().all(|i| *i < 8) || triangle.iter().all(|i| *i >= 8)
+            ));
+            assert!((area(&mesh.to_triangles()) - 30.0).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn indexed_mesh_in_3d_keeps_the_exact_vertices() {
+        let ring = Ring::new([
+            DVec3::new(0.1, 0.0, 0.3),
+            DVec3::new(1.1, 0.2, 0.3),
+            DVec3::new(1.0, 1.0, 1.3),
+            DVec3::new(0.0, 0.8, 1.3),
+        ]);
+
+        let mesh = ring.triangulate_indexed(Default::default()).unwrap();
+
+        assert_eq!(mesh.vertices, ring.points_open());
+        assert_eq!(mesh.indices.len(), 2);
+    }
+
+    #[test]
+    fn refined_mesh_in_3d_far_from_the_origin() {
+        let offset = DVec3::new(512_345.0, 5_432_109.0, 250.0);
+        // a square on a slope rising along x
+        let ring = Ring::new(
+            [DVec3::ZERO, DVec3::new(1.0, 0.0, 1.0), DVec3::ONE, DVec3::Y].map(|p| p + offset),
+        );
+        let refinement = RefinementConfig {
+            max_area: 0.05,
+            ..Default::default()
+        };
+
+        let mesh = ring
+            .triangulate_indexed(config(TriangulationMethod::Refined(refinement)))
+            .unwrap();
+
+        assert!(mesh.vertices.len() > 4);
+        for p in &mesh.vertices {
+            let local = *p - offset;
+            assert!((local.z - local.x).abs() < 1e-9, "{local}");
+            assert!((-1e-9..=1.0 + 1e-9).contains(&local.x), "{local}");
+            assert!((-1e-9..=1.0 + 1e-9).contains(&local.y), "{local}");
+        }
+    }
+
+    #[test]
+    fn refined_triangles_satisfy_the_bounds() {
+        // a long and thin slab with a notch
+        let slab = Polygon::new(
+            Ring::new([
+                Vec2::ZERO,
+                Vec2::new(10.0, 0.0),
+                Vec2::new(10.0, 1.0),
+                Vec2::new(5.5, 1.0),
+                Vec2::new(5.0, 0.5),
+                Vec2::new(4.5, 1.0),
+                Vec2::new(0.0, 1.0),
+            ]),
+            MultiRing(vec![Ring::new(
+                [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y].map(|p| p * 0.2 + Vec2::new(8.0, 0.4)),
+            )]),
+        );
+        let min_angle = 25f64.to_radians();
+        let refinement = RefinementConfig {
+            min_angle,
+            max_area: 0.1,
+            ..Default::default()
+        };
+
+        let mesh = slab
+            .triangulate_indexed(config(TriangulationMethod::Refined(refinement)))
+            .unwrap();
+        let triangles = mesh.to_triangles();
+
+        // the input vertices come first and are kept
+        let input = slab
+            .iter_rings()
+            .flat_map(|ring| ring.points_open().iter().copied())
+            .collect::<Vec<_>>();
+        assert_eq!(mesh.vertices[..input.len()], input);
+        assert!(mesh.vertices.len() > input.len());
+        assert!(mesh.sources[input.len()..].iter().all(Option::is_none));
+
+        assert!((area(&triangles) - slab.area()).abs() < 0.001);
+        for triangle in triangles.0 {
+            assert!(triangle.area() > 0.0 && triangle.area() <= 0.1 + 1e-6);
+            let [a, b, c] = triangle.0;
+            for (p, q, r) in [(a, b, c), (b, c, a), (c, a, b)] {
+                let angle = (q - p).angle_to(r - p).abs() as f64;
+                assert!(angle >= min_angle - 1e-4, "{triangle:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn refinement_fails_when_running_out_of_vertices() {
+        let square = Ring::new([Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]).to_polygon();
+        let refinement = RefinementConfig {
+            max_area: 0.01,
+            max_additional_vertices: Some(5),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            square.triangulate(config(TriangulationMethod::Refined(refinement))),
+            Err(GeometryError::NotConverged)
+        ));
+        let enough = RefinementConfig {
+            max_additional_vertices: Some(1000),
+            ..refinement
+        };
+        assert!(square
+            .triangulate(config(TriangulationMethod::Refined(enough)))
+            .is_ok());
+    }
+}
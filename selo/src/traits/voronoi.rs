@@ -0,0 +1,244 @@
+use bevy_math::{DVec2, Vec2};
+use spade::{DelaunayTriangulation, Point2, Triangulation as _};
+
+use crate::{primitives::*, BoolOps as _, Map, Point};
+
+/// Delaunay triangulations and Voronoi diagrams of point sets.
+///
+/// The Voronoi cell of a point is the area which is closer to this point than to any other point
+/// of the set. Cells are unbounded at the border of the set, [`Voronoi::voronoi_cells`] bounds them
+/// by a box around the points, [`Voronoi::voronoi_cells_in`] clips them to an area instead.
+/// Non-finite points are ignored and get empty cells, duplicated points get the same cell.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let room = Ring::new([Vec2::ZERO, Vec2::X * 4.0, Vec2::new(4.0, 2.0), Vec2::Y * 2.0]);
+/// let sensors = vec![Vec2::new(1.0, 1.0), Vec2::new(3.0, 1.0)];
+///
+/// let zones = sensors.voronoi_cells_in(&room.to_polygon());
+///
+/// // each sensor covers one half of the room
+/// assert_eq!(zones.len(), 2);
+/// assert!(zones.iter().all(|zone| (zone.area() - 4.0).abs() < 0.0001));
+/// ```
+pub trait Voronoi {
+    type P: Point;
+
+    /// The Delaunay triangulation of the points with counter-clockwise triangles
+    fn delaunay(&self) -> MultiTriangle<Self::P>;
+
+    /// One counter-clockwise cell per point, bounded by a box around the points
+    fn voronoi_cells(&self) -> Vec<Polygon<Self::P>>;
+
+    /// One cell per point clipped to `bounds`. The cells may consist of several parts if `bounds`
+    /// isn't convex.
+    fn voronoi_cells_in(&self, bounds: &Polygon<Self::P>) -> Vec<MultiPolygon<Self::P>>;
+}
+
+fn triangulation(points: &[DVec2]) -> (DelaunayTriangulation<Point2<f64>>, Vec<Option<usize>>) {
+    let mut triangulation = DelaunayTriangulation::<Point2<f64>>::new();
+    let handles = points
+        .iter()
+        .map(|p| {
+            triangulation
+                .insert(Point2::new(p.x, p.y))
+                .ok()
+                .map(|handle| handle.index())
+        })
+        .collect();
+    (triangulation, handles)
+}
+
+fn to_dvec2(p: Point2<f64>) -> DVec2 {
+    DVec2::new(p.x, p.y)
+}
+
+/// Clips a convex counter-clockwise polygon to the half plane closer to `site` than to `other`
+fn clip_half_plane(polygon: Vec<DVec2>, site: DVec2, other: DVec2) -> Vec<DVec2> {
+    let normal = other - site;
+    let center = (site + other) * 0.5;
+    let distance = |p: DVec2| (p - center).dot(normal);
+
+    let mut clipped = vec![];
+    for (&a, &b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+        let (da, db) = (distance(a), distance(b));
+        if da <= 0.0 {
+            clipped.push(a);
+        }
+        if (da < 0.0 && db > 0.0) || (da > 0.0 && db < 0.0) {
+            clipped.push(a.lerp(b, da / (da - db)));
+        }
+    }
+    clipped
+}
+
+fn cells_in_box(points: &[DVec2], min: DVec2, max: DVec2) -> Vec<Polygon<DVec2>> {
+    let (triangulation, handles) = triangulation(points);
+    let corners = vec![min, DVec2::new(max.x, min.y), max, DVec2::new(min.x, max.y)];
+
+    points
+        .iter()
+        .zip(handles)
+        .map(|(site, handle)| {
+            let Some(handle) = handle else {
+                return Polygon::default();
+            };
+            let vertex =
+                triangulation.vertex(spade::handles::FixedVertexHandle::from_index(handle));
+            let cell = vertex.out_edges().fold(corners.clone(), |cell, edge| {
+                clip_half_plane(cell, *site, to_dvec2(edge.to().position()))
+            });
+            Ring::new(cell).to_polygon()
+        })
+        .collect()
+}
+
+/// The bounding box of the points, enlarged so that every cell has some extent
+fn bounding_box<'a>(points: impl IntoIterator<Item = &'a DVec2>) -> (DVec2, DVec2) {
+    let (min, max) = points
+        .into_iter()
+        .filter(|p| p.is_finite())
+        .fold((DVec2::INFINITY, DVec2::NEG_INFINITY), |(min, max), p| {
+            (min.min(*p), max.max(*p))
+        });
+    if !min.is_finite() {
+        return (DVec2::ZERO, DVec2::ZERO);
+    }
+    let margin = (max - min).max_element().max(1.0);
+    (min - margin, max + margin)
+}
+
+impl Voronoi for [DVec2] {
+    type P = DVec2;
+
+    fn delaunay(&self) -> MultiTriangle<DVec2> {
+        let (triangulation, _) = triangulation(self);
+        MultiTriangle(
+            triangulation
+                .inner_faces()
+                .map(|face| Triangle(face.vertices().map(|vertex| to_dvec2(vertex.position()))))
+                .collect(),
+        )
+    }
+
+    fn voronoi_cells(&self) -> Vec<Polygon<DVec2>> {
+        let (min, max) = bounding_box(self);
+        cells_in_box(self, min, max)
+    }
+
+    fn voronoi_cells_in(&self, bounds: &Polygon<DVec2>) -> Vec<MultiPolygon<DVec2>> {
+        let (min, max) = bounding_box(self.iter().chain(bounds.exterior().points_open()));
+        cells_in_box(self, min, max)
+            .into_iter()
+            .map(|cell| {
+                if cell.exterior().points_open().is_empty() {
+                    MultiPolygon::empty()
+                } else {
+                    cell.intersection(bounds)
+                }
+            })
+            .collect()
+    }
+}
+
+impl Voronoi for [Vec2] {
+    type P = Vec2;
+
+    fn delaunay(&self) -> MultiTriangle<Vec2> {
+        to_dvec2s(self).delaunay().map(|p| p.as_vec2())
+    }
+
+    fn voronoi_cells(&self) -> Vec<Polygon<Vec2>> {
+        to_dvec2s(self)
+            .voronoi_cells()
+            .into_iter()
+            .map(|cell| cell.map(|p| p.as_vec2()))
+            .collect()
+    }
+
+    fn voronoi_cells_in(&self, bounds: &Polygon<Vec2>) -> Vec<MultiPolygon<Vec2>> {
+        to_dvec2s(self)
+            .voronoi_cells_in(&bounds.map(|p| p.as_dvec2()))
+            .into_iter()
+            .map(|cells| cells.map(|p| p.as_vec2()))
+            .collect()
+    }
+}
+
+fn to_dvec2s(points: &[Vec2]) -> Vec<DVec2> {
+    points.iter().map(|p| p.as_dvec2()).collect()
+}
+
+#[cfg(test)]
+mod voronoi_tests {
+    use super::*;
+    use crate::Area;
+
+    #[test]
+    fn delaunay_of_a_square_grid() {
+        let points = (0..3)
+            .flat_map(|x| (0..3).map(move |y| DVec2::new(x as f64, y as f64)))
+            .collect::<Vec<_>>();
+
+        let triangles = points.delaunay();
+
+        assert_eq!(triangles.0.len(), 8);
+        assert!(triangles.0.iter().all(|t| (t.area() - 0.5).abs() < 1e-9));
+    }
+
+    #[test]
+    fn cells_are_closest_to_their_point() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.5),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(-1.0, 1.5),
+            Vec2::new(0.5, 0.8),
+        ];
+
+        let cells = points.voronoi_cells();
+
+        assert_eq!(cells.len(), points.len());
+        for (i, cell) in cells.iter().enumerate() {
+            assert!(cell.exterior().area() > 0.0);
+            let center = cell.exterior().points_open().iter().sum::<Vec2>()
+                / cell.exterior().points_open().len() as f32;
+            let closest = (0..points.len())
+                .min_by(|a, b| {
+                    points[*a]
+                        .distance(center)
+                        .total_cmp(&points[*b].distance(center))
+                })
+                .unwrap();
+            assert_eq!(closest, i);
+        }
+        // the cells tile the box around the points
+        let total = cells.iter().map(|cell| cell.area()).sum::<f32>();
+        assert!((total - 9.0 * 8.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn cells_clipped_to_a_concave_room() {
+        // an L-shaped room
+        let room = Ring::new([
+            Vec2::ZERO,
+            Vec2::new(4.0, 0.0),
+            Vec2::new(4.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 4.0),
+            Vec2::new(0.0, 4.0),
+        ])
+        .to_polygon();
+        let points = [Vec2::new(0.5, 0.5), Vec2::new(3.5, 3.5), Vec2::NAN];
+
+        let cells = points.voronoi_cells_in(&room);
+
+        // the far point covers both ends of the L
+        assert_eq!(cells[1].len(), 2);
+        assert!(cells[2].is_empty());
+        let total = cells.iter().map(|cell| cell.area()).sum::<f32>();
+        assert!((total - room.area()).abs() < 0.001);
+    }
+}
@@ -15,6 +15,11 @@ use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 pub struct Workplane {
     pub plane: prelude::InfinitePlane3d,
     pub origin: Vec3,
+    /// The direction in the plane which becomes the X axis of the embedded 2D coordinates, see
+    /// [`Workplane::with_x_axis`]. If `None`, the shortest rotation of the normal onto `Vec3::Z`
+    /// determines the orientation.
+    #[serde(default)]
+    x_axis: Option<Dir3>,
 }
 
 impl Workplane {
@@ -23,6 +28,7 @@ impl Workplane {
         Self {
             plane: InfinitePlane3d::new(normal),
             origin,
+            x_axis: None,
         }
     }
 
@@ -37,6 +43,7 @@ impl Workplane {
         Ok(Self {
             plane: InfinitePlane3d::new(normal),
             origin: p.iter_points().next().unwrap(),
+            x_axis: None,
         })
     }
 
@@ -53,7 +60,48 @@ impl Workplane {
     #[inline]
     pub fn from_three_points([a, b, c]: [Vec3; 3]) -> Self {
         let (plane, origin) = InfinitePlane3d::from_points(a, b, c);
-        Self { plane, origin }
+        Self {
+            plane,
+            origin,
+            x_axis: None,
+        }
+    }
+
+    /// The least squares plane through any number of points, e.g. of a scanned or imported face
+    /// which is only nearly planar. The origin is the centroid of the points, the normal agrees
+    /// with their winding if they have one. Use the deviations to decide whether embedding the
+    /// points into the plane is safe, see [`Planarity`](crate::Planarity).
+    ///
+    /// Fails for fewer than three points or if they are collinear.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use selo::prelude::*;
+    /// let face = Ring::new([
+    ///     Vec3::new(0.0, 0.0, 0.01),
+    ///     Vec3::new(2.0, 0.0, -0.01),
+    ///     Vec3::new(2.0, 2.0, 0.01),
+    ///     Vec3::new(0.0, 2.0, -0.01),
+    /// ]);
+    ///
+    /// let fit = Workplane::best_fit(&face).unwrap();
+    ///
+    /// assert!(fit.workplane.normal().abs_diff_eq(Vec3::Z, 1e-6));
+    /// assert!(fit.workplane.origin().abs_diff_eq(Vec3::ONE.with_z(0.0), 1e-6));
+    /// assert!((fit.max_deviation - 0.01).abs() < 1e-6);
+    /// assert!((fit.rms_deviation - 0.01).abs() < 1e-6);
+    /// ```
+    pub fn best_fit<T: IterPoints<P = Vec3>>(points: &T) -> Result<PlaneFit<Self>, GeometryError> {
+        let (normal, origin, max_deviation, rms_deviation) =
+            crate::algorithms::fit_plane(points.iter_points().map(|p| p.as_dvec3()))
+                .ok_or(GeometryError::InvalidGeometry)?;
+        let normal = Dir3::new(normal.as_vec3()).map_err(|_| GeometryError::InvalidGeometry)?;
+        Ok(PlaneFit {
+            workplane: Self::from_normal_and_origin(normal, origin.as_vec3()),
+            max_deviation: max_deviation as f32,
+            rms_deviation: rms_deviation as f32,
+        })
     }
 
     /// puts the origin at the position with minimum distance to Vec3::ZERO
@@ -84,9 +132,83 @@ impl Workplane {
         self.plane.normal
     }
 
+    /// Aligns the X axis of the embedded 2D coordinates with `direction`, e.g. with an edge of the
+    /// embedded geometry. The direction is projected into the plane first. Fails if it is parallel
+    /// to the normal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use selo::prelude::*;
+    /// let roof = Workplane::from_normal_and_origin(Dir3::new(Vec3::new(0.0, -1.0, 1.0)).unwrap(), Vec3::Z)
+    ///     .with_x_axis(Vec3::X)
+    ///     .unwrap();
+    ///
+    /// let eave = Line([Vec3::new(0.0, 0.0, 1.0), Vec3::new(3.0, 0.0, 1.0)]).embed(roof);
+    ///
+    /// assert!((eave.dst() - eave.src()).abs_diff_eq(Vec2::new(3.0, 0.0), 1e-6));
+    /// assert!(roof.with_x_axis(roof.normal().as_vec3()).is_err());
+    /// ```
+    #[inline]
+    pub fn with_x_axis(self, direction: Vec3) -> Result<Self, GeometryError> {
+        let x_axis =
+            Dir3::new(self.in_plane(direction)?).map_err(|_| GeometryError::InvalidGeometry)?;
+        Ok(Self {
+            x_axis: Some(x_axis),
+            ..self
+        })
+    }
+
+    /// Orients the embedded 2D coordinates such that `up` points along their Y axis, e.g.
+    /// `Vec3::Z` to keep world-up up on walls. The direction is projected into the plane first.
+    /// Fails if it is parallel to the normal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use selo::prelude::*;
+    /// let wall = Workplane::from_normal_and_origin(Dir3::NEG_Y, Vec3::ZERO)
+    ///     .with_up(Vec3::Z)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(wall.x_axis(), Dir3::X);
+    /// assert!(Vec3::new(2.0, 0.0, 3.0).embed(wall).abs_diff_eq(Vec2::new(2.0, 3.0), 1e-6));
+    /// ```
+    #[inline]
+    pub fn with_up(self, up: Vec3) -> Result<Self, GeometryError> {
+        self.with_x_axis(self.in_plane(up)?.cross(self.plane.normal.as_vec3()))
+    }
+
+    /// The part of `direction` which lies in the plane, fails if there's hardly anything left
+    fn in_plane(&self, direction: Vec3) -> Result<Vec3, GeometryError> {
+        let normal = self.plane.normal.as_vec3();
+        let projected = direction - normal * normal.dot(direction);
+        if projected.length() > direction.length() * f32::EPSILON * 16.0 {
+            Ok(projected)
+        } else {
+            Err(GeometryError::InvalidGeometry)
+        }
+    }
+
+    /// The direction in 3D of the X axis of the embedded 2D coordinates
+    #[inline]
+    pub fn x_axis(&self) -> Dir3 {
+        self.x_axis
+            .unwrap_or_else(|| self.xy_projection_rotation().inverse() * Dir3::X)
+    }
+
+    /// The rotation which turns the plane into the XY plane, with the normal pointing along
+    /// `Vec3::Z` and the [`x_axis`](Workplane::x_axis) along `Vec3::X`
     #[inline]
     pub fn xy_projection_rotation(&self) -> Quat {
-        Quat::from_rotation_arc(self.plane.normal.as_vec3(), Vec3::Z)
+        let normal = self.plane.normal.as_vec3();
+        match self.x_axis {
+            Some(x_axis) => {
+                let x_axis = x_axis.as_vec3();
+                Quat::from_mat3(&Mat3::from_cols(x_axis, normal.cross(x_axis), normal)).inverse()
+            }
+            None => Quat::from_rotation_arc(normal, Vec3::Z),
+        }
     }
 
     #[inline]
@@ -122,4 +244,592 @@ impl Workplane {
         let primitive_2d = primitive.embed(*self);
         f(primitive_2d).unembed(*self)
     }
+
+    /// The line in which two planes intersect, or `None` if they are parallel. The first point of
+    /// the returned line is the point of the intersection closest to the origin of `self`, the
+    /// second one is a unit step along the direction `self.normal() × other.normal()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use selo::prelude::*;
+    /// let floor = Workplane::from_normal_and_origin(Dir3::Z, Vec3::ZERO);
+    /// let wall = Workplane::from_normal_and_origin(Dir3::X, Vec3::new(2.0, 5.0, 5.0));
+    ///
+    /// let junction = floor.intersect_plane(&wall).unwrap();
+    ///
+    /// assert!(junction.src().abs_diff_eq(Vec3::new(2.0, 0.0, 0.0), 1e-6));
+    /// assert!(junction.dst().abs_diff_eq(Vec3::new(2.0, 1.0, 0.0), 1e-6));
+    /// assert!(floor.intersect_plane(&floor).is_none());
+    /// ```
+    pub fn intersect_plane(&self, other: &Workplane) -> Option<Line<Vec3>> {
+        let (n1, n2) = (self.normal().as_vec3(), other.normal().as_vec3());
+        let direction = n1.cross(n2);
+        if direction.length_squared() < f32::EPSILON * f32::EPSILON {
+            return None;
+        }
+        let (d1, d2) = (n1.dot(self.origin), n2.dot(other.origin));
+        let point =
+            (n2.cross(direction) * d1 + direction.cross(n1) * d2) / direction.length_squared();
+
+        let direction = direction.normalize();
+        let closest = point + direction * (self.origin - point).dot(direction);
+        Some(Line([closest, closest + direction]))
+    }
+
+    /// The point in which the infinite line through `line` intersects the plane, together with
+    /// the parameter `t` of the point such that it equals `src + t * (dst - src)`. The
+    /// intersection lies on the segment itself if `t` is within `0.0..=1.0`. Returns `None` if the
+    /// line is parallel to the plane.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use selo::prelude::*;
+    /// let floor = Workplane::from_normal_and_origin(Dir3::Z, Vec3::Z);
+    /// let column = Line([Vec3::new(1.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 4.0)]);
+    ///
+    /// let (point, t) = floor.intersect_line(&column).unwrap();
+    ///
+    /// assert_eq!(point, Vec3::new(1.0, 1.0, 1.0));
+    /// assert_eq!(t, 0.25);
+    /// ```
+    pub fn intersect_line(&self, line: &Line<Vec3>) -> Option<(Vec3, f32)> {
+        let direction = line.dst() - line.src();
+        let denominator = self.normal().dot(direction);
+        if denominator.abs() <= f32::EPSILON * direction.length() {
+            return None;
+        }
+        let t = self.normal().dot(self.origin - line.src()) / denominator;
+        Some((line.src() + direction * t, t))
+    }
+
+    /// The segments in which the plane cuts through a polygon. A convex polygon results in at most
+    /// one segment, a concave one or one with holes possibly in several. The segments run along
+    /// `self.normal() × polygon.normal()`. Vertices exactly on the plane count as lying on the
+    /// side the normal points to, so a polygon lying in the plane results in no segments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use selo::prelude::*;
+    /// // a U-shaped wall, cut at half height
+    /// let wall = Ring::new([
+    ///     Vec3::ZERO,
+    ///     Vec3::new(3.0, 0.0, 0.0),
+    ///     Vec3::new(3.0, 0.0, 2.0),
+    ///     Vec3::new(2.0, 0.0, 2.0),
+    ///     Vec3::new(2.0, 0.0, 0.5),
+    ///     Vec3::new(1.0, 0.0, 0.5),
+    ///     Vec3::new(1.0, 0.0, 2.0),
+    ///     Vec3::new(0.0, 0.0, 2.0),
+    /// ])
+    /// .to_polygon();
+    /// let plane = Workplane::from_normal_and_origin(Dir3::Z, Vec3::Z);
+    ///
+    /// let chords = plane.intersect_polygon(&wall);
+    ///
+    /// assert_eq!(chords.len(), 2);
+    /// ```
+    pub fn intersect_polygon(&self, polygon: &Polygon<Vec3>) -> Vec<Line<Vec3>> {
+        let polygon = polygon.map(|p| p.as_dvec3());
+        let rings = polygon
+            .iter_rings()
+            .map(|ring| ring.points_open())
+            .collect::<Vec<_>>();
+        crate::algorithms::chords(
+            self.normal().as_dvec3(),
+            self.origin.as_dvec3(),
+            polygon.normal(),
+            &rings,
+        )
+        .into_iter()
+        .map(|chord| chord.map(|p| p.as_vec3()))
+        .filter(|[a, b]| a != b)
+        .map(Line)
+        .collect()
+    }
+}
+
+/// A [`Workplane`] with double precision, for geometry with `DVec3` coordinates which is embedded
+/// into `DVec2` coordinates. Use this for geometry far away from the origin, e.g. site models in
+/// projected coordinates, where the precision of `f32` doesn't suffice.
+///
+/// # Example
+///
+/// ```
+/// # use selo::prelude::*;
+/// let offset = DVec3::new(400_000.0, 5_000_000.0, 0.0);
+/// let corners = [DVec3::ZERO, DVec3::X, DVec3::new(1.0, 1.0, 0.0), DVec3::Y];
+/// let plot = Ring::new(corners.map(|p| p * 0.01 + offset));
+///
+/// let plane = DWorkplane::from_primitive(&plot).unwrap();
+/// let flat = plot.embed(plane);
+///
+/// let [a, b, ..] = flat.points_open() else { unreachable!() };
+/// assert!((a.distance(*b) - 0.01).abs() < 1e-9);
+/// assert!(flat.unembed(plane).points_open()[2].abs_diff_eq(plot.points_open()[2], 1e-9));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Serialize, Deserialize)
+)]
+pub struct DWorkplane {
+    /// The unit normal of the plane
+    pub normal: DVec3,
+    pub origin: DVec3,
+    /// The unit direction in the plane which becomes the X axis of the embedded 2D coordinates, see
+    /// [`Workplane::x_axis`]
+    #[serde(default)]
+    x_axis: Option<DVec3>,
+}
+
+impl DWorkplane {
+    /// Fails if the normal can't be normalized
+    #[inline]
+    pub fn from_normal_and_origin(normal: DVec3, origin: DVec3) -> Result<Self, GeometryError> {
+        let normal = normal
+            .try_normalize()
+            .ok_or(GeometryError::InvalidGeometry)?;
+        Ok(Self {
+            normal,
+            origin,
+            x_axis: None,
+        })
+    }
+
+    #[inline]
+    pub fn from_primitive<P: IterPoints<P = DVec3> + Normal<P = DVec3>>(
+        p: &P,
+    ) -> Result<Self, GeometryError> {
+        let origin = p
+            .iter_points()
+            .next()
+            .ok_or(GeometryError::InvalidGeometry)?;
+        Self::from_normal_and_origin(p.normal(), origin)
+    }
+
+    /// Create a new `DWorkplane` based on three points, with the geometric center of the points as
+    /// origin. The direction of the normal is determined by the winding order of the points.
+    /// Fails if the points are collinear.
+    #[inline]
+    pub fn from_three_points([a, b, c]: [DVec3; 3]) -> Result<Self, GeometryError> {
+        Self::from_normal_and_origin((b - a).cross(c - a), (a + b + c) / 3.0)
+    }
+
+    /// See [`Workplane::best_fit`]
+    pub fn best_fit<T: IterPoints<P = DVec3>>(points: &T) -> Result<PlaneFit<Self>, GeometryError> {
+        let (normal, origin, max_deviation, rms_deviation) =
+            crate::algorithms::fit_plane(points.iter_points())
+                .ok_or(GeometryError::InvalidGeometry)?;
+        Ok(PlaneFit {
+            workplane: Self::from_normal_and_origin(normal, origin)?,
+            max_deviation,
+            rms_deviation,
+        })
+    }
+
+    /// puts the origin at the position with minimum distance to DVec3::ZERO, see
+    /// [`Workplane::hesse_normal_form`]
+    #[inline]
+    pub fn hesse_normal_form(self) -> Self {
+        Self {
+            origin: self.normal * self.origin.dot(self.normal),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn origin(&self) -> DVec3 {
+        self.origin
+    }
+
+    #[inline]
+    pub fn normal(&self) -> DVec3 {
+        self.normal
+    }
+
+    /// See [`Workplane::with_x_axis`]
+    #[inline]
+    pub fn with_x_axis(self, direction: DVec3) -> Result<Self, GeometryError> {
+        let x_axis = self
+            .in_plane(direction)?
+            .try_normalize()
+            .ok_or(GeometryError::InvalidGeometry)?;
+        Ok(Self {
+            x_axis: Some(x_axis),
+            ..self
+        })
+    }
+
+    /// See [`Workplane::with_up`]
+    #[inline]
+    pub fn with_up(self, up: DVec3) -> Result<Self, GeometryError> {
+        self.with_x_axis(self.in_plane(up)?.cross(self.normal))
+    }
+
+    /// See [`Workplane::in_plane`]
+    fn in_plane(&self, direction: DVec3) -> Result<DVec3, GeometryError> {
+        let projected = direction - self.normal * self.normal.dot(direction);
+        if projected.length() > direction.length() * f64::EPSILON * 16.0 {
+            Ok(projected)
+        } else {
+            Err(GeometryError::InvalidGeometry)
+        }
+    }
+
+    /// See [`Workplane::x_axis`]
+    #[inline]
+    pub fn x_axis(&self) -> DVec3 {
+        self.x_axis
+            .unwrap_or_else(|| self.xy_projection_rotation().inverse() * DVec3::X)
+    }
+
+    /// See [`Workplane::xy_projection_rotation`]
+    #[inline]
+    pub fn xy_projection_rotation(&self) -> DQuat {
+        match self.x_axis {
+            Some(x_axis) => DQuat::from_mat3(&DMat3::from_cols(
+                x_axis,
+                self.normal.cross(x_axis),
+                self.normal,
+            ))
+            .inverse(),
+            None => DQuat::from_rotation_arc(self.normal, DVec3::Z),
+        }
+    }
+
+    #[inline]
+    pub fn xy_projection(&self) -> DAffine3 {
+        let rotation = self.xy_projection_rotation();
+        let transformed_origin = rotation * self.origin;
+        DAffine3::from_translation(-DVec3::Z * transformed_origin.z) * DAffine3::from_quat(rotation)
+    }
+
+    #[inline]
+    pub fn xy_injection(&self) -> DAffine3 {
+        self.xy_projection().inverse()
+    }
+
+    #[inline]
+    pub fn xy_projection_injection(&self) -> (DAffine3, DAffine3) {
+        let projection = self.xy_projection();
+        (projection, projection.inverse())
+    }
+
+    #[inline]
+    pub fn project_point(&self, pos: DVec3) -> DVec3 {
+        let dist = self.normal.dot(pos - self.origin);
+        pos - dist * self.normal
+    }
+
+    #[inline]
+    pub fn transform<T: Embed<Self>, O: Unembed<Self>>(
+        &self,
+        primitive: T,
+        f: impl FnOnce(T::Type2D) -> O,
+    ) -> O::Type3D {
+        let primitive_2d = primitive.embed(*self);
+        f(primitive_2d).unembed(*self)
+    }
+
+    /// The plane with single precision
+    #[inline]
+    pub fn as_workplane(&self) -> Workplane {
+        Workplane {
+            x_axis: self
+                .x_axis
+                .and_then(|x_axis| Dir3::new(x_axis.as_vec3()).ok()),
+            ..Workplane::from_normal_and_origin(
+                Dir3::new(self.normal.as_vec3()).unwrap_or(Dir3::Z),
+                self.origin.as_vec3(),
+            )
+        }
+    }
+}
+
+impl From<Workplane> for DWorkplane {
+    #[inline]
+    fn from(workplane: Workplane) -> Self {
+        Self {
+            normal: workplane.normal().as_dvec3(),
+            origin: workplane.origin().as_dvec3(),
+            x_axis: workplane.x_axis.map(|x_axis| x_axis.as_dvec3()),
+        }
+    }
+}
+
+/// A plane fitted through points by [`Workplane::best_fit`] or [`DWorkplane::best_fit`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaneFit<W: EmbeddingPlane> {
+    pub workplane: W,
+    /// The largest distance of any of the points to the plane
+    pub max_deviation: <W::P as Point>::S,
+    /// The root mean square of the distances of the points to the plane
+    pub rms_deviation: <W::P as Point>::S,
+}
+
+/// What's needed from a workplane independent of its precision. Implemented by [`Workplane`] and
+/// [`DWorkplane`].
+pub trait EmbeddingPlane: Copy + std::fmt::Debug {
+    type P: Point3;
+
+    /// See [`Workplane::from_primitive`]
+    fn from_primitive<T: IterPoints<P = Self::P> + Normal<P = Self::P>>(
+        primitive: &T,
+    ) -> Result<Self, GeometryError>;
+
+    /// See [`Workplane::best_fit`]
+    fn best_fit<T: IterPoints<P = Self::P>>(points: &T) -> Result<PlaneFit<Self>, GeometryError>;
+
+    /// See [`Workplane::hesse_normal_form`]
+    fn hesse_normal_form(self) -> Self;
+
+    /// The unit normal and the signed distance of the plane to the origin of the coordinate system
+    fn normal_and_distance(&self) -> (Self::P, <Self::P as Point>::S);
+
+    /// Whether the normals and the origins of both planes are equal within the tolerance
+    fn abs_diff_eq(&self, other: &Self, tolerance: <Self::P as Point>::S) -> bool;
+}
+
+impl EmbeddingPlane for Workplane {
+    type P = Vec3;
+
+    #[inline]
+    fn from_primitive<T: IterPoints<P = Vec3> + Normal<P = Vec3>>(
+        primitive: &T,
+    ) -> Result<Self, GeometryError> {
+        Workplane::from_primitive(primitive)
+    }
+
+    #[inline]
+    fn best_fit<T: IterPoints<P = Vec3>>(points: &T) -> Result<PlaneFit<Self>, GeometryError> {
+        Workplane::best_fit(points)
+    }
+
+    #[inline]
+    fn hesse_normal_form(self) -> Self {
+        Workplane::hesse_normal_form(self)
+    }
+
+    #[inline]
+    fn normal_and_distance(&self) -> (Vec3, f32) {
+        (self.normal().as_vec3(), self.normal().dot(self.origin))
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, tolerance: f32) -> bool {
+        self.normal().abs_diff_eq(*other.normal(), tolerance)
+            && self.origin.abs_diff_eq(other.origin, tolerance)
+    }
+}
+
+impl EmbeddingPlane for DWorkplane {
+    type P = DVec3;
+
+    #[inline]
+    fn from_primitive<T: IterPoints<P = DVec3> + Normal<P = DVec3>>(
+        primitive: &T,
+    ) -> Result<Self, GeometryError> {
+        DWorkplane::from_primitive(primitive)
+    }
+
+    #[inline]
+    fn best_fit<T: IterPoints<P = DVec3>>(points: &T) -> Result<PlaneFit<Self>, GeometryError> {
+        DWorkplane::best_fit(points)
+    }
+
+    #[inline]
+    fn hesse_normal_form(self) -> Self {
+        DWorkplane::hesse_normal_form(self)
+    }
+
+    #[inline]
+    fn normal_and_distance(&self) -> (DVec3, f64) {
+        (self.normal, self.normal.dot(self.origin))
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.normal.abs_diff_eq(other.normal, tolerance)
+            && self.origin.abs_diff_eq(other.origin, tolerance)
+    }
+}
+
+/// The 3D point types together with the workplane of the same precision
+pub trait WorkplanePoint: Point3 {
+    type Workplane: EmbeddingPlane<P = Self>;
+}
+
+impl WorkplanePoint for Vec3 {
+    type Workplane = Workplane;
+}
+
+impl WorkplanePoint for DVec3 {
+    type Workplane = DWorkplane;
+}
+
+#[cfg(test)]
+mod workplane_tests {
+    use super::*;
+
+    #[test]
+    fn intersection_of_tilted_planes() {
+        let a = Workplane::from_normal_and_origin(
+            Dir3::new(Vec3::new(1.0, 0.0, 1.0)).unwrap(),
+            Vec3::Z,
+        );
+        let b = Workplane::from_normal_and_origin(
+            Dir3::new(Vec3::new(0.0, 1.0, 1.0)).unwrap(),
+            Vec3::X,
+        );
+
+        let line = a.intersect_plane(&b).unwrap();
+
+        for p in [line.src(), line.dst(), line.src() * 3.0 - line.dst() * 2.0] {
+            assert!(a.normal().dot(p - a.origin()).abs() < 1e-5);
+            assert!(b.normal().dot(p - b.origin()).abs() < 1e-5);
+        }
+        assert!((line.length() - 1.0).abs() < 1e-5);
+        // the line passes as close as possible to the origin of the first plane
+        assert!((a.origin() - line.src()).dot(line.dst() - line.src()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn line_parallel_to_the_plane() {
+        let floor = Workplane::from_normal_and_origin(Dir3::Z, Vec3::ZERO);
+
+        assert!(floor.intersect_line(&Line([Vec3::Z, Vec3::ONE])).is_none());
+        let (point, t) = floor
+            .intersect_line(&Line([Vec3::Z, Vec3::new(0.0, 0.0, 2.0)]))
+            .unwrap();
+        assert_eq!(point, Vec3::ZERO);
+        assert_eq!(t, -1.0);
+    }
+
+    #[test]
+    fn chords_through_a_hole() {
+        let square = |size: f32, offset: Vec3| {
+            Ring::new(
+                [Vec3::ZERO, Vec3::X, Vec3::new(1.0, 0.0, 1.0), Vec3::Z].map(|p| p * size + offset),
+            )
+        };
+        let wall = Polygon::new(
+            square(3.0, Vec3::ZERO),
+            square(1.0, Vec3::new(1.0, 0.0, 1.0)).to_multi(),
+        );
+        let plane = Workplane::from_normal_and_origin(Dir3::Z, Vec3::Z * 1.5);
+
+        let chords = plane.intersect_polygon(&wall);
+
+        assert_eq!(chords.len(), 2);
+        let length = chords.iter().map(|chord| chord.length()).sum::<f32>();
+        assert!((length - 2.0).abs() < 1e-5);
+        assert!(chords.iter().all(|chord| chord.src().x < chord.dst().x));
+
+        let above = Workplane::from_normal_and_origin(Dir3::Z, Vec3::Z * 4.0);
+        assert!(above.intersect_polygon(&wall).is_empty());
+    }
+
+    #[test]
+    fn x_axis_is_stable_near_negative_z() {
+        let corner = Vec3::new(1.0, 2.0, 0.0);
+        for tilt in [-1e-4, 0.0, 1e-4] {
+            let normal = Dir3::new(Vec3::new(tilt, 0.0, -1.0)).unwrap();
+            let plane = Workplane::from_normal_and_origin(normal, Vec3::ZERO)
+                .with_x_axis(Vec3::X)
+                .unwrap();
+
+            let flat = corner.embed(plane);
+
+            // looking from below the plane mirrors the Y axis
+            assert!(flat.abs_diff_eq(Vec2::new(1.0, -2.0), 1e-3), "{flat:?}");
+            assert!(flat.unembed(plane).abs_diff_eq(corner, 1e-3));
+            assert!(plane.x_axis().abs_diff_eq(Vec3::X, 1e-3));
+        }
+    }
+
+    #[test]
+    fn default_x_axis() {
+        let plane = Workplane::from_normal_and_origin(Dir3::new(Vec3::ONE).unwrap(), Vec3::Z);
+        let x_axis = plane.x_axis();
+        let aligned = plane.with_x_axis(x_axis.as_vec3()).unwrap();
+
+        assert!(x_axis.dot(plane.normal().as_vec3()).abs() < 1e-6);
+        let p = Vec3::new(3.0, -1.0, 2.0);
+        assert!(p.embed(plane).abs_diff_eq(p.embed(aligned), 1e-5));
+        assert!(plane.with_up(Vec3::ONE).is_err());
+    }
+}
+
+#[cfg(test)]
+mod dworkplane_tests {
+    use super::*;
+
+    #[test]
+    fn embedding_far_from_the_origin() {
+        // a tilted square of 1mm at a few hundred kilometres
+        let offset = DVec3::new(512_345.678, 5_432_109.876, 321.0);
+        let square = Polygon::new(
+            Ring::new(
+                [
+                    DVec3::ZERO,
+                    DVec3::X,
+                    DVec3::new(1.0, 1.0, 1.0),
+                    DVec3::new(0.0, 1.0, 1.0),
+                ]
+                .map(|p| p * 0.001 + offset),
+            ),
+            MultiRing::empty(),
+        );
+        let plane = DWorkplane::from_primitive(&square).unwrap();
+
+        let flat = square.embed(plane);
+
+        assert!((flat.area() - 0.000_001 * 2f64.sqrt()).abs() < 1e-12);
+        let back = flat.unembed(plane);
+        for (a, b) in back.iter_points().zip(square.iter_points()) {
+            assert!(a.abs_diff_eq(b, 1e-9));
+        }
+        // single precision can't even tell the points apart
+        let single = plane.as_workplane();
+        let flat = square.map(|p| p.as_vec3()).embed(single);
+        assert!((flat.area() as f64 - 0.000_001 * 2f64.sqrt()).abs() > 1e-8);
+    }
+
+    #[test]
+    fn degenerate_planes() {
+        assert!(DWorkplane::from_normal_and_origin(DVec3::ZERO, DVec3::ONE).is_err());
+        assert!(DWorkplane::from_three_points([DVec3::ZERO, DVec3::X, DVec3::X * 2.0]).is_err());
+        let plane =
+            DWorkplane::from_three_points([DVec3::Z, DVec3::Z + DVec3::X, DVec3::Z + DVec3::Y])
+                .unwrap();
+        assert_eq!(plane.normal(), DVec3::Z);
+        assert_eq!(plane.hesse_normal_form().origin(), DVec3::Z);
+        let hesse = plane.hesse_normal_form();
+        assert_eq!(DWorkplane::from(hesse.as_workplane()), hesse);
+        assert!(plane.with_x_axis(DVec3::Z).is_err());
+    }
+
+    #[test]
+    fn wall_with_world_up() {
+        let wall = DWorkplane::from_normal_and_origin(DVec3::new(1.0, -1.0, 0.0), DVec3::ZERO)
+            .unwrap()
+            .with_up(DVec3::Z)
+            .unwrap();
+
+        let window = DVec3::new(1.0, 1.0, 2.0).embed(wall);
+
+        assert!(window.abs_diff_eq(DVec2::new(2f64.sqrt(), 2.0), 1e-12));
+        assert!(wall
+            .x_axis()
+            .abs_diff_eq(DVec3::new(1.0, 1.0, 0.0).normalize(), 1e-12));
+        let single = wall.as_workplane();
+        assert!(DVec3::new(1.0, 1.0, 2.0)
+            .as_vec3()
+            .embed(single)
+            .abs_diff_eq(Vec2::new(2f32.sqrt(), 2.0), 1e-5));
+    }
 }